@@ -0,0 +1,239 @@
+//! Credentials and authenticated API client.
+
+use std::fmt;
+
+use super::{Client, Location, Algorithm, Btc};
+use error::{Result, Error};
+use hashrate::Hashrate;
+use types::{Order, PoolInfo, NewOrder, Balance, BuyInfo};
+
+/// Access level of an API key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyAccess {
+    /// Read-only API key, it can only be used to get the orders and the balance of the account.
+    ReadOnly,
+    /// Full access API key, it can also be used to create and modify orders.
+    Full,
+}
+
+/// Credentials of a [nicehash.com](https://www.nicehash.com/) account.
+///
+/// The API key is not shown in the `Debug` output, so that it does not end up in logs.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Credentials {
+    api_id: u64,
+    api_key: String,
+    access: KeyAccess,
+}
+
+impl Credentials {
+    /// Creates new credentials with a full access API key.
+    pub fn new<S: Into<String>>(api_id: u64, api_key: S) -> Credentials {
+        Credentials {
+            api_id: api_id,
+            api_key: api_key.into(),
+            access: KeyAccess::Full,
+        }
+    }
+
+    /// Creates new credentials with a read-only API key.
+    pub fn read_only<S: Into<String>>(api_id: u64, api_key: S) -> Credentials {
+        Credentials {
+            api_id: api_id,
+            api_key: api_key.into(),
+            access: KeyAccess::ReadOnly,
+        }
+    }
+
+    /// Gets the API ID of the account.
+    pub fn get_api_id(&self) -> u64 {
+        self.api_id
+    }
+
+    /// Gets the API key of the account.
+    pub fn get_api_key(&self) -> &str {
+        &self.api_key
+    }
+
+    /// Gets the access level of the API key.
+    pub fn get_access(&self) -> KeyAccess {
+        self.access
+    }
+
+    /// Checks that the API key can be used for the given mutating API method.
+    fn check_full_access(&self, method: &str) -> Result<()> {
+        if self.access == KeyAccess::Full {
+            Ok(())
+        } else {
+            Err(Error::ReadOnlyKey(format!("the `{}` method requires a full access API key, but \
+                                            a read-only API key was given",
+                                           method)))
+        }
+    }
+}
+
+impl fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Credentials")
+            .field("api_id", &self.api_id)
+            .field("api_key", &"<redacted>")
+            .field("access", &self.access)
+            .finish()
+    }
+}
+
+/// [nicehash.com](https://www.nicehash.com/) API client bound to the credentials of an account.
+///
+/// It exposes the private API methods without having to pass the API ID and key in every call.
+/// Methods that modify orders will fail without calling the API if the credentials have a
+/// read-only API key.
+#[derive(Debug)]
+pub struct AuthenticatedClient<'c> {
+    client: &'c Client,
+    credentials: Credentials,
+}
+
+impl<'c> AuthenticatedClient<'c> {
+    /// Creates a new authenticated client from a client and the credentials of an account.
+    pub fn new(client: &'c Client, credentials: Credentials) -> AuthenticatedClient<'c> {
+        AuthenticatedClient {
+            client: client,
+            credentials: credentials,
+        }
+    }
+
+    /// Gets the client used to perform the requests.
+    pub fn get_client(&self) -> &Client {
+        self.client
+    }
+
+    /// Gets the credentials bound to this client.
+    pub fn get_credentials(&self) -> &Credentials {
+        &self.credentials
+    }
+
+    /// Gets orders for the current user.
+    pub fn get_my_orders(&self, location: Location, algorithm: Algorithm) -> Result<Vec<Order>> {
+        self.client.get_my_orders(self.credentials.api_id,
+                                  &self.credentials.api_key,
+                                  location,
+                                  algorithm)
+    }
+
+    /// Creates a new order.
+    pub fn create_order(&self,
+                        location: Location,
+                        order: NewOrder,
+                        pool: PoolInfo,
+                        code: Option<u32>)
+                        -> Result<u64> {
+        self.credentials.check_full_access("orders.create")?;
+        self.client.create_order(self.credentials.api_id,
+                                 &self.credentials.api_key,
+                                 location,
+                                 order,
+                                 pool,
+                                 code)
+    }
+
+    /// Creates a new order, after checking it with the given buy information.
+    pub fn create_order_checked(&self,
+                                location: Location,
+                                order: NewOrder,
+                                pool: PoolInfo,
+                                code: Option<u32>,
+                                buy_info: &BuyInfo)
+                                -> Result<u64> {
+        self.credentials.check_full_access("orders.create")?;
+        self.client.create_order_checked(self.credentials.api_id,
+                                         &self.credentials.api_key,
+                                         location,
+                                         order,
+                                         pool,
+                                         code,
+                                         buy_info)
+    }
+
+    /// Refills the given order with the given amount.
+    pub fn refill_order(&self,
+                        location: Location,
+                        algorithm: Algorithm,
+                        order_id: u64,
+                        amount: Btc)
+                        -> Result<()> {
+        self.credentials.check_full_access("orders.refill")?;
+        self.client.refill_order(self.credentials.api_id,
+                                 &self.credentials.api_key,
+                                 location,
+                                 algorithm,
+                                 order_id,
+                                 amount)
+    }
+
+    /// Removes the given order.
+    pub fn remove_order(&self,
+                        location: Location,
+                        algorithm: Algorithm,
+                        order_id: u64)
+                        -> Result<()> {
+        self.credentials.check_full_access("orders.remove")?;
+        self.client.remove_order(self.credentials.api_id,
+                                 &self.credentials.api_key,
+                                 location,
+                                 algorithm,
+                                 order_id)
+    }
+
+    /// Sets the price to the given order.
+    pub fn set_order_price(&self,
+                           location: Location,
+                           algorithm: Algorithm,
+                           order_id: u64,
+                           price: Btc)
+                           -> Result<()> {
+        self.credentials.check_full_access("orders.set.price")?;
+        self.client.set_order_price(self.credentials.api_id,
+                                    &self.credentials.api_key,
+                                    location,
+                                    algorithm,
+                                    order_id,
+                                    price)
+    }
+
+    /// Decrease the price to the given order.
+    ///
+    /// The same restrictions as in `Client::decrease_order_price()` apply.
+    pub fn decrease_order_price(&self,
+                                location: Location,
+                                algorithm: Algorithm,
+                                order_id: u64)
+                                -> Result<Btc> {
+        self.credentials.check_full_access("orders.set.price.decrease")?;
+        self.client.decrease_order_price(self.credentials.api_id,
+                                         &self.credentials.api_key,
+                                         location,
+                                         algorithm,
+                                         order_id)
+    }
+
+    /// Sets the speed limit for the given order.
+    pub fn set_order_speed_limit(&self,
+                                 location: Location,
+                                 algorithm: Algorithm,
+                                 order_id: u64,
+                                 speed_limit: Option<Hashrate>)
+                                 -> Result<()> {
+        self.credentials.check_full_access("orders.set.limit")?;
+        self.client.set_order_speed_limit(self.credentials.api_id,
+                                          &self.credentials.api_key,
+                                          location,
+                                          algorithm,
+                                          order_id,
+                                          speed_limit)
+    }
+
+    /// Gets the balance of the account.
+    pub fn get_balance(&self) -> Result<Balance> {
+        self.client.get_balance(self.credentials.api_id, &self.credentials.api_key)
+    }
+}
@@ -0,0 +1,251 @@
+//! Exact BTC amounts.
+//!
+//! Amounts, prices and fees are stored as an integer number of satoshis, so that adding and
+//! subtracting them does not accumulate rounding errors. They are parsed from and formatted to the
+//! decimal strings used by the API, with up to 8 decimals.
+
+use std::fmt;
+use std::ops::{Add, AddAssign, Sub, SubAssign, Neg};
+use std::str::FromStr;
+
+use error::{Result, Error};
+
+/// Number of satoshis in a BTC.
+pub const SATOSHIS_PER_BTC: i64 = 100_000_000;
+
+/// Exact amount of BTCs, with satoshi precision.
+///
+/// Amounts can be negative, for example, the price step of an algorithm. The `+`, `-`, `+=` and
+/// `-=` operators, negation and `abs()` panic on overflow, in debug and release mode, while the
+/// `checked_*` methods return `None`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Btc {
+    satoshis: i64,
+}
+
+/// Order fee, in BTCs.
+pub const ORDER_FEE: Btc = Btc { satoshis: 10_000 };
+
+impl Btc {
+    /// Creates an amount from a number of satoshis.
+    pub fn from_satoshis(satoshis: i64) -> Btc {
+        Btc { satoshis: satoshis }
+    }
+
+    /// Creates an amount of zero BTCs.
+    pub fn zero() -> Btc {
+        Btc::from_satoshis(0)
+    }
+
+    /// Creates an amount from a float number of BTCs, rounded to the nearest satoshi.
+    ///
+    /// It returns `None` if the float is not finite or the amount does not fit in an `i64` of
+    /// satoshis.
+    pub fn from_f64(btc: f64) -> Option<Btc> {
+        let satoshis = (btc * SATOSHIS_PER_BTC as f64).round();
+        if satoshis.is_finite() && satoshis.abs() < i64::max_value() as f64 {
+            Some(Btc::from_satoshis(satoshis as i64))
+        } else {
+            None
+        }
+    }
+
+    /// Gets the amount as a number of satoshis.
+    pub fn get_satoshis(&self) -> i64 {
+        self.satoshis
+    }
+
+    /// Gets the amount as a float number of BTCs.
+    ///
+    /// This is only meant for calculations that do not need exact results, such as statistics.
+    pub fn to_f64(&self) -> f64 {
+        self.satoshis as f64 / SATOSHIS_PER_BTC as f64
+    }
+
+    /// Checks if the amount is zero.
+    pub fn is_zero(&self) -> bool {
+        self.satoshis == 0
+    }
+
+    /// Checks if the amount is negative.
+    pub fn is_negative(&self) -> bool {
+        self.satoshis < 0
+    }
+
+    /// Gets the absolute value of the amount.
+    ///
+    /// # Panics
+    ///
+    /// It panics if the amount is `i64::min_value()` satoshis, since its absolute value does not
+    /// fit in a `Btc`. Use `checked_abs()` to get `None` instead.
+    pub fn abs(&self) -> Btc {
+        self.checked_abs().expect("overflow when getting the absolute value of a BTC amount")
+    }
+
+    /// Gets the absolute value of the amount, returning `None` on overflow.
+    pub fn checked_abs(&self) -> Option<Btc> {
+        self.satoshis.checked_abs().map(Btc::from_satoshis)
+    }
+
+    /// Negates the amount, returning `None` on overflow.
+    pub fn checked_neg(&self) -> Option<Btc> {
+        self.satoshis.checked_neg().map(Btc::from_satoshis)
+    }
+
+    /// Adds two amounts, returning `None` on overflow.
+    pub fn checked_add(&self, other: Btc) -> Option<Btc> {
+        self.satoshis.checked_add(other.satoshis).map(Btc::from_satoshis)
+    }
+
+    /// Subtracts two amounts, returning `None` on overflow.
+    pub fn checked_sub(&self, other: Btc) -> Option<Btc> {
+        self.satoshis.checked_sub(other.satoshis).map(Btc::from_satoshis)
+    }
+
+    /// Multiplies the amount by an integer, returning `None` on overflow.
+    pub fn checked_mul(&self, factor: i64) -> Option<Btc> {
+        self.satoshis.checked_mul(factor).map(Btc::from_satoshis)
+    }
+
+    /// Multiplies the amount by an exact rate in thousandths, such as a fee, rounding half away
+    /// from zero to the nearest satoshi.
+    ///
+    /// It returns `None` on overflow.
+    pub fn checked_mul_per_mille(&self, per_mille: i64) -> Option<Btc> {
+        let whole = match (self.satoshis / 1000).checked_mul(per_mille) {
+            Some(whole) => whole,
+            None => return None,
+        };
+        let rest = match (self.satoshis % 1000).checked_mul(per_mille) {
+            Some(rest) => rest,
+            None => return None,
+        };
+        // The division truncates towards zero, so adding half rounds half away from zero.
+        let half = if rest < 0 { -500 } else { 500 };
+        rest.checked_add(half)
+            .and_then(|rest| whole.checked_add(rest / 1000))
+            .map(Btc::from_satoshis)
+    }
+
+    /// Multiplies the amount by a rate, such as the speed of an order, rounding to the nearest
+    /// satoshi.
+    ///
+    /// It returns `None` on overflow.
+    pub fn checked_mul_rate(&self, rate: f64) -> Option<Btc> {
+        let satoshis = (self.satoshis as f64 * rate).round();
+        if satoshis.is_finite() && satoshis.abs() < i64::max_value() as f64 {
+            Some(Btc::from_satoshis(satoshis as i64))
+        } else {
+            None
+        }
+    }
+}
+
+/// # Panics
+///
+/// It panics on overflow. Use `Btc::checked_add()` to get `None` instead.
+impl Add for Btc {
+    type Output = Btc;
+
+    fn add(self, other: Btc) -> Btc {
+        self.checked_add(other).expect("overflow when adding BTC amounts")
+    }
+}
+
+/// # Panics
+///
+/// It panics on overflow. Use `Btc::checked_sub()` to get `None` instead.
+impl Sub for Btc {
+    type Output = Btc;
+
+    fn sub(self, other: Btc) -> Btc {
+        self.checked_sub(other).expect("overflow when subtracting BTC amounts")
+    }
+}
+
+/// # Panics
+///
+/// It panics on overflow. Use `Btc::checked_add()` to get `None` instead.
+impl AddAssign for Btc {
+    fn add_assign(&mut self, other: Btc) {
+        *self = *self + other;
+    }
+}
+
+/// # Panics
+///
+/// It panics on overflow. Use `Btc::checked_sub()` to get `None` instead.
+impl SubAssign for Btc {
+    fn sub_assign(&mut self, other: Btc) {
+        *self = *self - other;
+    }
+}
+
+/// # Panics
+///
+/// It panics if the amount is `i64::min_value()` satoshis. Use `Btc::checked_neg()` to get `None`
+/// instead.
+impl Neg for Btc {
+    type Output = Btc;
+
+    fn neg(self) -> Btc {
+        self.checked_neg().expect("overflow when negating a BTC amount")
+    }
+}
+
+/// Parses an amount in BTCs, such as `0.00010000`, `-0.001` or `2`.
+///
+/// The amount can have up to 8 decimals, more decimals would be below the satoshi precision.
+impl FromStr for Btc {
+    type Err = Error;
+
+    fn from_str(btc: &str) -> Result<Btc> {
+        let invalid = || Error::ParseBtc(format!("invalid BTC amount `{}`", btc));
+
+        let (negative, digits) = if btc.starts_with('-') {
+            (true, &btc[1..])
+        } else {
+            (false, btc)
+        };
+        let (integer, fraction) = match digits.find('.') {
+            Some(point) => (&digits[..point], &digits[point + 1..]),
+            None => (digits, ""),
+        };
+        if (integer.is_empty() && fraction.is_empty()) || fraction.len() > 8 ||
+           !integer.chars().chain(fraction.chars()).all(|c| c.is_digit(10)) {
+            return Err(invalid());
+        }
+
+        let integer: i64 = if integer.is_empty() {
+            0
+        } else {
+            integer.parse().map_err(|_| invalid())?
+        };
+        let mut fraction_satoshis: i64 = if fraction.is_empty() {
+            0
+        } else {
+            fraction.parse().map_err(|_| invalid())?
+        };
+        for _ in fraction.len()..8 {
+            fraction_satoshis *= 10;
+        }
+        let satoshis = integer.checked_mul(SATOSHIS_PER_BTC)
+            .and_then(|satoshis| satoshis.checked_add(fraction_satoshis))
+            .ok_or_else(invalid)?;
+
+        Ok(Btc::from_satoshis(if negative { -satoshis } else { satoshis }))
+    }
+}
+
+/// Formats the amount with 8 decimals, as the API does.
+impl fmt::Display for Btc {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sign = if self.satoshis < 0 { "-" } else { "" };
+        let satoshis = self.satoshis.wrapping_abs() as u64;
+        write!(f,
+               "{}{}.{:08}",
+               sign,
+               satoshis / SATOSHIS_PER_BTC as u64,
+               satoshis % SATOSHIS_PER_BTC as u64)
+    }
+}
@@ -0,0 +1,221 @@
+//! Cost and duration of orders.
+//!
+//! Orders spend their BTCs at a rate given by their price and their speed: the price is paid per
+//! unit of speed per day. On top of the BTCs spent in hashing power, each new order pays the order
+//! fee, and all the hashing funds pay the service fee. These calculators combine both to estimate
+//! how long an order will run and how much it will cost.
+//!
+//! The estimates use the speed as constant, and they fail with `Error::Overflow` if the amounts
+//! do not fit in a `Btc`.
+
+use std::cmp;
+use std::time::Duration;
+
+use super::{Btc, ORDER_FEE, DAY, calculate_service_fee};
+use error::{Result, Error};
+use hashrate::Hashrate;
+use types::{AlgoBuyInfo, NewOrder, Order};
+
+/// Estimated cost and duration of a new order.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrderCost {
+    amount: Btc,
+    service_fee: Btc,
+    total: Btc,
+    btc_per_day: Option<Btc>,
+}
+
+impl OrderCost {
+    /// Estimates the cost of a new order, running at its speed limit.
+    ///
+    /// The buy information must be the one of the algorithm of the order. Orders without limit
+    /// get all the hashrate they can, so their duration cannot be estimated. It fails if the limit
+    /// cannot be converted to the native unit of the algorithm.
+    pub fn new(order: &NewOrder, buy_info: &AlgoBuyInfo) -> Result<OrderCost> {
+        let btc_per_day = match order.limit {
+            Some(limit) => Some(btc_per_day(order.price, limit.to_native(buy_info)?, buy_info)?),
+            None => None,
+        };
+        OrderCost::with_btc_per_day(order, btc_per_day)
+    }
+
+    /// Estimates the cost of a new order, running at the given speed.
+    ///
+    /// It is useful for orders without limit, or for orders that are not expected to reach their
+    /// limit. It fails if the speed cannot be converted to the native unit of the algorithm.
+    pub fn at_speed(order: &NewOrder,
+                    buy_info: &AlgoBuyInfo,
+                    speed: Hashrate)
+                    -> Result<OrderCost> {
+        let btc_per_day = btc_per_day(order.price, speed.to_native(buy_info)?, buy_info)?;
+        OrderCost::with_btc_per_day(order, Some(btc_per_day))
+    }
+
+    /// Creates the cost of a new order that spends the given BTCs per day, if known.
+    fn with_btc_per_day(order: &NewOrder, btc_per_day: Option<Btc>) -> Result<OrderCost> {
+        let service_fee = calculate_service_fee(order.amount)
+            .ok_or_else(|| {
+                Error::AmountBelowMinimum(format!("order amount {} BTC is negative", order.amount))
+            })?;
+        let total = order.amount
+            .checked_add(service_fee)
+            .and_then(|total| total.checked_add(ORDER_FEE))
+            .ok_or_else(|| {
+                Error::Overflow(format!("the total cost of an order of {} BTC overflows",
+                                        order.amount))
+            })?;
+        Ok(OrderCost {
+            amount: order.amount,
+            service_fee: service_fee,
+            total: total,
+            btc_per_day: btc_per_day,
+        })
+    }
+
+    /// Gets the BTCs of the order spent in hashing power.
+    pub fn get_amount(&self) -> Btc {
+        self.amount
+    }
+
+    /// Gets the order fee.
+    pub fn get_order_fee(&self) -> Btc {
+        ORDER_FEE
+    }
+
+    /// Gets the service fee of the hashing funds.
+    pub fn get_service_fee(&self) -> Btc {
+        self.service_fee
+    }
+
+    /// Gets the total cost of the order, including the order and service fees.
+    pub fn get_total(&self) -> Btc {
+        self.total
+    }
+
+    /// Gets the BTCs spent per day, if the speed of the order is known.
+    pub fn get_btc_per_day(&self) -> Option<Btc> {
+        self.btc_per_day
+    }
+
+    /// Gets the time until the order spends all its BTCs, if the speed of the order is known.
+    ///
+    /// It returns `None` if the order does not spend anything, for example, with a zero speed.
+    pub fn get_duration(&self) -> Option<Duration> {
+        self.btc_per_day.and_then(|btc_per_day| duration_of(self.amount, btc_per_day))
+    }
+}
+
+/// Estimated refill needed to keep an existing order alive.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RefillEstimate {
+    btc_per_day: Btc,
+    btc_available: Option<Btc>,
+    needed: Btc,
+    amount: Btc,
+    service_fee: Btc,
+    cost: Btc,
+}
+
+impl RefillEstimate {
+    /// Estimates the refill needed to keep the order alive for the given duration.
+    ///
+    /// The order is expected to keep its current price and accepted speed. The remaining BTCs of
+    /// the order are only known for the orders of the account, returned by `get_my_orders()`; for
+    /// other orders, the whole duration has to be paid by the refill. The buy information must be
+    /// the one of the algorithm of the order.
+    pub fn new(order: &Order,
+               buy_info: &AlgoBuyInfo,
+               duration: Duration)
+               -> Result<RefillEstimate> {
+        let btc_per_day = btc_per_day(order.get_price(), order.get_accepted_speed(), buy_info)?;
+        let btc_available = order.get_btc_available();
+        let days = (duration.as_secs() as f64 + duration.subsec_nanos() as f64 / 1e9) /
+                   DAY as f64;
+        let needed = btc_per_day.checked_mul_rate(days)
+            .ok_or_else(|| {
+                Error::Overflow(format!("the BTCs spent by order {} in {} days overflow",
+                                        order.get_id(),
+                                        days))
+            })?;
+        let amount = match btc_available {
+            Some(available) if available >= needed => Btc::zero(),
+            Some(available) => needed - available,
+            None => needed,
+        };
+        let service_fee = calculate_service_fee(amount)
+            .ok_or_else(|| {
+                Error::AmountBelowMinimum(format!("refill amount {} BTC is negative", amount))
+            })?;
+        let cost = amount.checked_add(service_fee)
+            .ok_or_else(|| {
+                Error::Overflow(format!("the cost of a refill of {} BTC overflows", amount))
+            })?;
+
+        Ok(RefillEstimate {
+            btc_per_day: btc_per_day,
+            btc_available: btc_available,
+            needed: needed,
+            amount: amount,
+            service_fee: service_fee,
+            cost: cost,
+        })
+    }
+
+    /// Gets the BTCs spent per day by the order at its current speed.
+    pub fn get_btc_per_day(&self) -> Btc {
+        self.btc_per_day
+    }
+
+    /// Gets the time until the order spends its remaining BTCs, without refill.
+    ///
+    /// It returns `None` if the remaining BTCs of the order are unknown, or if the order does not
+    /// spend anything at its current speed.
+    pub fn get_remaining_time(&self) -> Option<Duration> {
+        self.btc_available.and_then(|available| duration_of(available, self.btc_per_day))
+    }
+
+    /// Gets the BTCs that the order will spend in the duration of the estimate.
+    pub fn get_needed(&self) -> Btc {
+        self.needed
+    }
+
+    /// Gets the BTCs that have to be added to the hashing funds of the order.
+    ///
+    /// It is zero if the order already has enough funds.
+    pub fn get_amount(&self) -> Btc {
+        self.amount
+    }
+
+    /// Gets the service fee of the refill.
+    pub fn get_service_fee(&self) -> Btc {
+        self.service_fee
+    }
+
+    /// Gets the total cost of the refill, including the service fee.
+    pub fn get_cost(&self) -> Btc {
+        self.cost
+    }
+}
+
+/// Calculates the BTCs spent per day by an order with the given price and speed.
+///
+/// The speed is multiplied by `AlgoBuyInfo::get_multiplier()` to get it in the units of the
+/// price. It fails if the result does not fit in a `Btc`.
+fn btc_per_day(price: Btc, speed: f64, buy_info: &AlgoBuyInfo) -> Result<Btc> {
+    price.checked_mul_rate(speed.max(0.0) * buy_info.get_multiplier())
+        .ok_or_else(|| {
+            Error::Overflow(format!("the BTCs spent per day at {} BTC and speed {} overflow",
+                                    price,
+                                    speed))
+        })
+}
+
+/// Calculates the time needed to spend the given amount at the given rate per day.
+fn duration_of(amount: Btc, btc_per_day: Btc) -> Option<Duration> {
+    if btc_per_day.get_satoshis() <= 0 {
+        return None;
+    }
+    let seconds = cmp::max(amount.get_satoshis(), 0) as f64 / btc_per_day.get_satoshis() as f64 *
+                  DAY as f64;
+    Some(Duration::from_secs(seconds.round() as u64))
+}
@@ -5,7 +5,7 @@ use std::error::Error as StdError;
 use std::fmt;
 use std::num::{ParseFloatError, ParseIntError};
 
-use hyper::error::Error as HyperError;
+use hyper::error::{Error as HyperError, ParseError as UrlError};
 use serde_json::error::Error as JsonError;
 use semver::SemVerError;
 
@@ -17,30 +17,92 @@ pub type Result<T> = StdResult<T, Error>;
 pub enum Error {
     /// Hyper request error.
     Hyper(HyperError),
+    /// URL parsing error.
+    Url(UrlError),
     /// JSON decoding error.
     Json(JsonError),
     /// SemVer parsing error.
     SemVer(SemVerError),
     /// API error.
     Api(String),
-    /// Unsuccessful result.
+    /// The request did not finish before the timeout of the client.
+    Timeout(String),
+    /// Unsuccessful result, with an unknown error message.
     Result(String),
+    /// Read-only API key used for a method that requires a full access API key.
+    ReadOnlyKey(String),
+    /// Incorrect API ID or key.
+    InvalidKey(String),
+    /// Not enough funds in the account.
+    InsufficientBalance(String),
+    /// Order price decreased before the minimum time between decreases.
+    PriceDecreaseTooSoon(String),
+    /// Unknown order, or order not owned by the account.
+    OrderNotFound(String),
+    /// Too many requests to the API.
+    RateLimited(String),
+    /// Order speed limit below the minimum limit of the algorithm.
+    LimitBelowMinimum(String),
+    /// Order amount below the minimum amount of a new order.
+    AmountBelowMinimum(String),
+    /// Empty pool host.
+    InvalidPoolHost(String),
+    /// Pool port `0`.
+    InvalidPoolPort(String),
+    /// Pool username longer than the maximum length.
+    PoolUsernameTooLong(String),
+    /// BTC amount too big for a calculation.
+    Overflow(String),
     /// Invalid algorithm.
     ParseAlgorithm(String),
     /// Invalid order type.
     ParseOrderType(String),
+    /// Invalid location.
+    ParseLocation(String),
+    /// Invalid hashrate or hashrate unit.
+    ParseHashrate(String),
+    /// Invalid BTC amount.
+    ParseBtc(String),
     /// Float parsing error.
     ParseFloat(ParseFloatError),
     /// Int parsing error.
     ParseInt(ParseIntError),
 }
 
+impl Error {
+    /// Creates an error from the message of an unsuccessful result of the API.
+    ///
+    /// Messages are matched exactly, and known messages are converted to their own variant. Any
+    /// other message, including a known one with a different wording, is returned as
+    /// `Error::Result`. The original message is kept in every variant.
+    pub fn from_result<S: Into<String>>(message: S) -> Error {
+        let message = message.into();
+        match message.as_str() {
+            "Incorrect key." => Error::InvalidKey(message),
+            "Read-only API key." => Error::ReadOnlyKey(message),
+            "Not enough funds." => Error::InsufficientBalance(message),
+            "Price decrease too soon." => Error::PriceDecreaseTooSoon(message),
+            "No such order." | "Order id incorrect." | "Unknown order id." => {
+                Error::OrderNotFound(message)
+            }
+            "Too many requests." => Error::RateLimited(message),
+            _ => Error::Result(message),
+        }
+    }
+}
+
 impl From<HyperError> for Error {
     fn from(err: HyperError) -> Error {
         Error::Hyper(err)
     }
 }
 
+impl From<UrlError> for Error {
+    fn from(err: UrlError) -> Error {
+        Error::Url(err)
+    }
+}
+
 impl From<JsonError> for Error {
     fn from(err: JsonError) -> Error {
         Error::Json(err)
@@ -75,13 +137,30 @@ impl StdError for Error {
     fn description(&self) -> &str {
         match *self {
             Error::Hyper(ref e) => e.description(),
+            Error::Url(ref e) => e.description(),
             Error::Json(ref e) => e.description(),
             Error::SemVer(ref e) => e.description(),
             Error::ParseFloat(ref e) => e.description(),
             Error::ParseInt(ref e) => e.description(),
             Error::Api(ref d) |
+            Error::Timeout(ref d) |
             Error::ParseAlgorithm(ref d) |
             Error::ParseOrderType(ref d) |
+            Error::ParseLocation(ref d) |
+            Error::ParseHashrate(ref d) |
+            Error::ParseBtc(ref d) |
+            Error::ReadOnlyKey(ref d) |
+            Error::InvalidKey(ref d) |
+            Error::InsufficientBalance(ref d) |
+            Error::PriceDecreaseTooSoon(ref d) |
+            Error::OrderNotFound(ref d) |
+            Error::RateLimited(ref d) |
+            Error::LimitBelowMinimum(ref d) |
+            Error::AmountBelowMinimum(ref d) |
+            Error::InvalidPoolHost(ref d) |
+            Error::InvalidPoolPort(ref d) |
+            Error::PoolUsernameTooLong(ref d) |
+            Error::Overflow(ref d) |
             Error::Result(ref d) => d,
         }
     }
@@ -89,6 +168,7 @@ impl StdError for Error {
     fn cause(&self) -> Option<&StdError> {
         match *self {
             Error::Hyper(ref e) => Some(e),
+            Error::Url(ref e) => Some(e),
             Error::Json(ref e) => Some(e),
             Error::SemVer(ref e) => Some(e),
             Error::ParseFloat(ref e) => Some(e),
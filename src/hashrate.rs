@@ -0,0 +1,237 @@
+//! Hashing speeds with explicit units.
+//!
+//! The API returns the speeds of each algorithm as bare numbers, in the unit given by
+//! `AlgoBuyInfo::get_speed_text()`: `MH/s` for some algorithms, `TH/s` or `Sol/s` for others.
+//! `Hashrate` keeps the unit together with the value, so that speeds can be converted to the
+//! native unit of each algorithm before sending them to the API. Bare speeds, as sent to the API,
+//! use `HashrateUnit::Native`.
+
+use std::fmt;
+use std::str::FromStr;
+
+use error::{Result, Error};
+use types::AlgoBuyInfo;
+
+/// Unit of a hashing speed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HashrateUnit {
+    /// Hashes per second.
+    Hash,
+    /// Kilohashes per second.
+    KiloHash,
+    /// Megahashes per second.
+    MegaHash,
+    /// Gigahashes per second.
+    GigaHash,
+    /// Terahashes per second.
+    TeraHash,
+    /// Petahashes per second.
+    PetaHash,
+    /// Solutions per second, used by Equihash.
+    Solution,
+    /// Kilosolutions per second.
+    KiloSolution,
+    /// Megasolutions per second.
+    MegaSolution,
+    /// Native unit of the algorithm, given by `AlgoBuyInfo::get_speed_text()`.
+    ///
+    /// It is the unit of the bare speeds of the API, and it cannot be converted to other units
+    /// without the buy information of the algorithm.
+    Native,
+}
+
+impl HashrateUnit {
+    /// Gets the number of hashes or solutions per second of one unit.
+    ///
+    /// The multiplier of the native unit depends on the algorithm, so it is `1`.
+    pub fn get_multiplier(&self) -> f64 {
+        match *self {
+            HashrateUnit::Hash | HashrateUnit::Solution | HashrateUnit::Native => 1.0,
+            HashrateUnit::KiloHash | HashrateUnit::KiloSolution => 1e3,
+            HashrateUnit::MegaHash | HashrateUnit::MegaSolution => 1e6,
+            HashrateUnit::GigaHash => 1e9,
+            HashrateUnit::TeraHash => 1e12,
+            HashrateUnit::PetaHash => 1e15,
+        }
+    }
+
+    /// Checks if the unit measures solutions instead of hashes.
+    ///
+    /// Speeds in solutions per second cannot be converted to hashes per second.
+    pub fn is_solution(&self) -> bool {
+        match *self {
+            HashrateUnit::Solution |
+            HashrateUnit::KiloSolution |
+            HashrateUnit::MegaSolution => true,
+            _ => false,
+        }
+    }
+
+    /// Gets the symbol of the unit, without the `/s` suffix, as used in the speed texts of the API.
+    ///
+    /// The native unit has no symbol, since the API sends bare speeds.
+    pub fn as_str(&self) -> &str {
+        match *self {
+            HashrateUnit::Hash => "H",
+            HashrateUnit::KiloHash => "kH",
+            HashrateUnit::MegaHash => "MH",
+            HashrateUnit::GigaHash => "GH",
+            HashrateUnit::TeraHash => "TH",
+            HashrateUnit::PetaHash => "PH",
+            HashrateUnit::Solution => "Sol",
+            HashrateUnit::KiloSolution => "kSol",
+            HashrateUnit::MegaSolution => "MSol",
+            HashrateUnit::Native => "",
+        }
+    }
+}
+
+/// Parses a unit such as `MH`, `GH/s` or `Sol/s`.
+impl FromStr for HashrateUnit {
+    type Err = Error;
+
+    fn from_str(unit: &str) -> Result<HashrateUnit> {
+        let trimmed = unit.trim();
+        let symbol = if trimmed.ends_with("/s") {
+            &trimmed[..trimmed.len() - 2]
+        } else {
+            trimmed
+        };
+        match symbol {
+            "H" => Ok(HashrateUnit::Hash),
+            "kH" | "KH" => Ok(HashrateUnit::KiloHash),
+            "MH" => Ok(HashrateUnit::MegaHash),
+            "GH" => Ok(HashrateUnit::GigaHash),
+            "TH" => Ok(HashrateUnit::TeraHash),
+            "PH" => Ok(HashrateUnit::PetaHash),
+            "Sol" => Ok(HashrateUnit::Solution),
+            "kSol" | "KSol" => Ok(HashrateUnit::KiloSolution),
+            "MSol" => Ok(HashrateUnit::MegaSolution),
+            _ => Err(Error::ParseHashrate(format!("unknown hashrate unit `{}`", unit))),
+        }
+    }
+}
+
+impl fmt::Display for HashrateUnit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            HashrateUnit::Native => write!(f, "native units"),
+            _ => write!(f, "{}/s", self.as_str()),
+        }
+    }
+}
+
+/// Hashing speed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hashrate {
+    value: f64,
+    unit: HashrateUnit,
+}
+
+impl Hashrate {
+    /// Creates a new hashrate with the given value and unit.
+    ///
+    /// # Panics
+    ///
+    /// It panics if the value is negative or NaN. Parse the hashrate or use `from_native()` to
+    /// get an error instead.
+    pub fn new(value: f64, unit: HashrateUnit) -> Hashrate {
+        assert!(value >= 0.0, "invalid hashrate value {}", value);
+        Hashrate {
+            value: value,
+            unit: unit,
+        }
+    }
+
+    /// Creates a hashrate from a speed in the native unit of an algorithm, as used by the API.
+    ///
+    /// It fails if the speed is negative or NaN.
+    pub fn from_native(speed: f64, buy_info: &AlgoBuyInfo) -> Result<Hashrate> {
+        check_value(speed)?;
+        Ok(Hashrate::new(speed, buy_info.get_speed_unit()?))
+    }
+
+    /// Gets the value of the hashrate, in its unit.
+    pub fn get_value(&self) -> f64 {
+        self.value
+    }
+
+    /// Gets the unit of the hashrate.
+    pub fn get_unit(&self) -> HashrateUnit {
+        self.unit
+    }
+
+    /// Gets the value of the hashrate in the given unit.
+    ///
+    /// It fails if one of the units measures hashes and the other one solutions, or if only one of
+    /// them is the native unit, since it depends on the algorithm.
+    pub fn value_in(&self, unit: HashrateUnit) -> Result<f64> {
+        if self.unit == unit {
+            return Ok(self.value);
+        }
+        if self.unit == HashrateUnit::Native || unit == HashrateUnit::Native ||
+           self.unit.is_solution() != unit.is_solution() {
+            return Err(Error::ParseHashrate(format!("cannot convert {} to {}", self.unit, unit)));
+        }
+        Ok(self.value * self.unit.get_multiplier() / unit.get_multiplier())
+    }
+
+    /// Converts the hashrate to the given unit.
+    pub fn convert_to(&self, unit: HashrateUnit) -> Result<Hashrate> {
+        Ok(Hashrate::new(self.value_in(unit)?, unit))
+    }
+
+    /// Gets the speed in the native unit of an algorithm, to be used in the API.
+    pub fn to_native(&self, buy_info: &AlgoBuyInfo) -> Result<f64> {
+        match self.unit {
+            HashrateUnit::Native => Ok(self.value),
+            _ => self.value_in(buy_info.get_speed_unit()?),
+        }
+    }
+}
+
+/// Parses a hashrate such as `1.5 GH/s`, `300Sol/s` or `1e3 MH/s`.
+///
+/// The unit is the suffix of letters and `/` at the end of the hashrate, so the value can use
+/// exponents. A bare speed, as used by the API, is in the native unit of the algorithm.
+impl FromStr for Hashrate {
+    type Err = Error;
+
+    fn from_str(hashrate: &str) -> Result<Hashrate> {
+        let trimmed = hashrate.trim();
+        let split = trimmed.char_indices()
+            .rev()
+            .find(|&(_, c)| !c.is_alphabetic() && c != '/')
+            .map_or(0, |(i, c)| i + c.len_utf8());
+        let (value, unit) = trimmed.split_at(split);
+        let value = value.trim()
+            .parse()
+            .map_err(|_| Error::ParseHashrate(format!("invalid hashrate `{}`", hashrate)))?;
+        check_value(value)?;
+        let unit = if unit.is_empty() {
+            HashrateUnit::Native
+        } else {
+            unit.parse()?
+        };
+        Ok(Hashrate::new(value, unit))
+    }
+}
+
+/// Checks that the value of a hashrate is not negative or NaN.
+fn check_value(value: f64) -> Result<()> {
+    if value >= 0.0 {
+        Ok(())
+    } else {
+        Err(Error::ParseHashrate(format!("invalid hashrate value {}, it must not be negative",
+                                         value)))
+    }
+}
+
+impl fmt::Display for Hashrate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.unit {
+            HashrateUnit::Native => write!(f, "{}", self.value),
+            _ => write!(f, "{} {}", self.value, self.unit),
+        }
+    }
+}
@@ -11,32 +11,67 @@
 extern crate hyper;
 extern crate semver;
 extern crate serde_json;
+#[cfg(feature = "serde")]
+extern crate serde;
 
-use std::ops::Deref;
+use std::io::{self, Cursor, Read};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
+use hyper::Url;
 use semver::Version;
 use serde_json::value::Value;
 use serde_json::de;
 
 pub mod error;
 pub mod types;
+pub mod transport;
+pub mod order_book;
+pub mod pricing;
+pub mod order_manager;
+pub mod refill;
+pub mod profit;
+pub mod hashrate;
+pub mod btc;
+pub mod cost;
+#[cfg(feature = "mock")]
+pub mod mock;
 mod public;
 mod private;
+mod auth;
+mod response;
+mod retry;
+mod rate_limit;
 
 use error::{Result, Error};
+use transport::{Transport, HttpTransport};
+use response::{ApiResponse, FromValue};
+pub use auth::{Credentials, KeyAccess, AuthenticatedClient};
+pub use retry::RetryPolicy;
+pub use rate_limit::{RateLimit, RateLimiter};
+pub use btc::{Btc, ORDER_FEE};
 
-/// Order fee, in BTCs.
-pub const ORDER_FEE: f64 = 0.0001;
-/// Service fee, in percent of order expenditure.
-pub const SERVICE_FEE: f64 = 0.03;
+/// Service fee, in thousandths of the order expenditure.
+pub const SERVICE_FEE_PER_MILLE: i64 = 30;
+
+/// Seconds in a day, the time unit of the prices and of the daily refill cap.
+pub const DAY: u64 = 24 * 60 * 60;
 
 const API_URL: &'static str = "https://www.nicehash.com/api";
 
 /// [nicehash.com](https://www.nicehash.com/) API client.
-#[derive(Debug)]
+///
+/// The client can be cloned cheaply, and all the clones share the same transport and rate
+/// limiter.
+#[derive(Debug, Clone)]
 pub struct Client {
-    inner: hyper::Client,
-    remote_version: Version,
+    transport: Arc<Transport>,
+    api_url: Url,
+    timeout: Option<Duration>,
+    retry_policy: RetryPolicy,
+    rate_limiter: RateLimiter,
+    max_pool_username_length: Option<usize>,
+    remote_version: Arc<Mutex<Option<Version>>>,
 }
 
 impl Client {
@@ -45,61 +80,334 @@ impl Client {
     /// It will also call the API to check that the connection works and to store the remote API
     /// version.
     pub fn new() -> Result<Client> {
-        Client::from_hyper_client(hyper::Client::new())
+        ClientBuilder::new().build()
+    }
+
+    /// Creates a new API client with default settings, without connecting to the API.
+    ///
+    /// The remote API version will be retrieved the first time it's requested with
+    /// `get_api_version()`, so this can be used when the API might not be reachable yet.
+    pub fn new_lazy() -> Result<Client> {
+        ClientBuilder::new().build_lazy()
     }
 
     /// Creates a new API client from a Hyper client.
     pub fn from_hyper_client(hyper_client: hyper::Client) -> Result<Client> {
-        let version = Client::remote_version(&hyper_client)?;
-        Ok(Client {
-            inner: hyper_client,
-            remote_version: version,
-        })
+        ClientBuilder::new().hyper_client(hyper_client).build()
     }
 
     /// Gets the version of the remote API.
     ///
-    /// This only returns the version stored in the client, it will not update the version if remote
-    /// API is updated while the client has been created.
-    pub fn get_api_version(&self) -> &Version {
-        &self.remote_version
+    /// This returns the version stored in the client, it will not update the version if remote
+    /// API is updated while the client has been created. If the client was created lazily and the
+    /// version has not been retrieved yet, it will call the API to get it.
+    pub fn get_api_version(&self) -> Result<Version> {
+        match self.get_cached_api_version() {
+            Some(version) => Ok(version),
+            None => self.update_api_version(),
+        }
+    }
+
+    /// Gets the version of the remote API stored in the client, without calling the API.
+    ///
+    /// It returns `None` if the client was created lazily and the version has not been retrieved
+    /// yet.
+    pub fn get_cached_api_version(&self) -> Option<Version> {
+        self.remote_version.lock().unwrap().clone()
+    }
+
+    /// Creates a new API client using the given HTTP transport.
+    pub fn from_transport<T: Transport + 'static>(transport: T) -> Result<Client> {
+        ClientBuilder::new().transport(transport).build()
+    }
+
+    /// Binds the given credentials to this client, to call the private API methods without
+    /// passing the API ID and key every time.
+    pub fn authenticated(&self, credentials: Credentials) -> AuthenticatedClient {
+        AuthenticatedClient::new(self, credentials)
+    }
+
+    /// Gets the HTTP transport used to perform the requests.
+    pub fn get_transport(&self) -> &Transport {
+        &*self.transport
+    }
+
+    /// Gets the base URL of the API this client connects to.
+    pub fn get_api_url(&self) -> &Url {
+        &self.api_url
+    }
+
+    /// Gets the timeout of each request, if any.
+    pub fn get_timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Creates a copy of this client with a different request timeout.
+    ///
+    /// This can be used to override the timeout for some calls, for example
+    /// `client.with_timeout(Some(Duration::from_secs(2))).get_orders(location, algorithm)`. The
+    /// new client shares the transport and the rate limiter of this client, and the timeout is
+    /// given to the transport in `Transport::get_with_timeout()`. `None` disables the timeout.
+    pub fn with_timeout(&self, timeout: Option<Duration>) -> Client {
+        let mut client = self.clone();
+        client.timeout = timeout;
+        client
+    }
+
+    /// Gets the policy used to retry the requests that fail with a transient error.
+    pub fn get_retry_policy(&self) -> RetryPolicy {
+        self.retry_policy
+    }
+
+    /// Gets the rate limiter of the requests of this client.
+    pub fn get_rate_limiter(&self) -> &RateLimiter {
+        &self.rate_limiter
+    }
+
+    /// Gets the maximum length of the pool usernames accepted in new orders, if it was set.
+    ///
+    /// If it was not set, `types::DEFAULT_MAX_POOL_USERNAME_LENGTH` is used.
+    pub fn get_max_pool_username_length(&self) -> Option<usize> {
+        self.max_pool_username_length
     }
 
     /// Updates the API version of this `Client`.
     ///
     /// This will check the current version of the remote server and store it in the `Client`
     /// object for later use.
-    pub fn update_api_version(&mut self) -> Result<&Version> {
-        let version = Client::remote_version(&self.inner)?;
-        self.remote_version = version;
-        Ok(&self.remote_version)
+    pub fn update_api_version(&self) -> Result<Version> {
+        // The lock is only taken to store the version, so other threads can read the stored
+        // version while the API is called.
+        let version = self.remote_version()?;
+        *self.remote_version.lock().unwrap() = Some(version.clone());
+        Ok(version)
     }
 
     /// Gets the version of the remote API.
-    fn remote_version(hyper_client: &hyper::Client) -> Result<Version> {
-        let response = hyper_client.get(API_URL).send()?;
-        let response_json: Value = de::from_reader(response)?;
-        if let Some(&Value::String(ref version)) =
-               response_json.find_path(&["result", "api_version"]) {
-            Ok(Version::parse(version)?)
-        } else {
-            Err(Error::Api("the api returned an invalid response for the version request"
-                .to_owned()))
+    fn remote_version(&self) -> Result<Version> {
+        retry::with_retries(&self.retry_policy, true, || {
+            rate_limit::acquire_public(&self.rate_limiter);
+            let response = self.get(self.api_url.clone())?;
+            let response_json: Value = de::from_reader(response)?;
+            if let Some(&Value::String(ref version)) =
+                   response_json.find_path(&["result", "api_version"]) {
+                Ok(Version::parse(version)?)
+            } else {
+                Err(Error::Api("the api returned an invalid response for the version request"
+                    .to_owned()))
+            }
+        })
+    }
+
+    /// Calls a public API method with the given URL and decodes the response.
+    fn public_call<T: FromValue>(&self, url: Url, response: ApiResponse<T>) -> Result<T> {
+        self.call(rate_limit::acquire_public, url, response, true)
+    }
+
+    /// Calls a private API method with the given URL and decodes the response.
+    fn private_call<T: FromValue>(&self,
+                                  url: Url,
+                                  response: ApiResponse<T>,
+                                  idempotent: bool)
+                                  -> Result<T> {
+        self.call(rate_limit::acquire_private, url, response, idempotent)
+    }
+
+    /// Calls the API with the given URL and decodes the response.
+    ///
+    /// Each attempt waits for the rate limiter with the given `acquire` function. If the request
+    /// fails with a transient error, it will be retried following the retry policy of the client.
+    /// Requests that are not idempotent are only retried if the policy allows it.
+    fn call<T: FromValue>(&self,
+                          acquire: fn(&RateLimiter),
+                          url: Url,
+                          response: ApiResponse<T>,
+                          idempotent: bool)
+                          -> Result<T> {
+        retry::with_retries(&self.retry_policy, idempotent, || {
+            acquire(&self.rate_limiter);
+            response.decode(self.get(url.clone())?)
+        })
+    }
+
+    /// Performs a request to the given URL with the transport of the client.
+    ///
+    /// The timeout of the client is given to the transport, and the whole response is read before
+    /// returning. If the transport times out while connecting, sending the request or reading the
+    /// response, the call fails with `Error::Timeout`.
+    fn get(&self, url: Url) -> Result<Box<Read>> {
+        let mut body = Vec::new();
+        let _ = self.transport
+            .get_with_timeout(url, self.timeout)
+            .and_then(|mut response| {
+                response.read_to_end(&mut body).map_err(|e| Error::Hyper(hyper::Error::Io(e)))
+            })
+            .map_err(|e| self.timeout_error(e))?;
+        Ok(Box::new(Cursor::new(body)))
+    }
+
+    /// Converts the I/O errors caused by the timeout of the client to `Error::Timeout`.
+    fn timeout_error(&self, error: Error) -> Error {
+        let timeout = match self.timeout {
+            Some(timeout) => timeout,
+            None => return error,
+        };
+        match error {
+            Error::Hyper(hyper::Error::Io(ref e)) if e.kind() == io::ErrorKind::TimedOut ||
+                                                     e.kind() == io::ErrorKind::WouldBlock => {
+                Error::Timeout(format!("the request did not finish in {}.{:03} seconds: {}",
+                                       timeout.as_secs(),
+                                       timeout.subsec_nanos() / 1_000_000,
+                                       e))
+            }
+            error => error,
         }
     }
 }
 
-impl Deref for Client {
-    type Target = hyper::Client;
+/// Builder for the [nicehash.com](https://www.nicehash.com/) API client.
+#[derive(Debug, Default)]
+pub struct ClientBuilder {
+    transport: Option<Arc<Transport>>,
+    api_url: Option<String>,
+    timeout: Option<Duration>,
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<RateLimiter>,
+    max_pool_username_length: Option<usize>,
+}
+
+impl ClientBuilder {
+    /// Creates a new client builder with default settings.
+    pub fn new() -> ClientBuilder {
+        ClientBuilder::default()
+    }
+
+    /// Sets the Hyper client used to perform the requests.
+    ///
+    /// This is a shortcut for `transport()` with a Hyper client.
+    pub fn hyper_client(self, hyper_client: hyper::Client) -> ClientBuilder {
+        self.transport(hyper_client)
+    }
+
+    /// Sets the HTTP transport used to perform the requests.
+    ///
+    /// By default, a new `HttpTransport` is used, with the timeout of the client.
+    pub fn transport<T: Transport + 'static>(mut self, transport: T) -> ClientBuilder {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
+    /// Sets the base URL of the API.
+    ///
+    /// By default, the client connects to `https://www.nicehash.com/api`. This can be used to
+    /// point the client to a different server, such as a local mock server for testing.
+    pub fn api_url<S: Into<String>>(mut self, api_url: S) -> ClientBuilder {
+        self.api_url = Some(api_url.into());
+        self
+    }
+
+    /// Sets the timeout of each request.
+    ///
+    /// The timeout is applied by the transport: the default `HttpTransport` sets it on the
+    /// sockets of the requests, so connecting to the server, sending the request and each read of
+    /// the response fail with `Error::Timeout` if they take longer than the timeout. A slow
+    /// response can take longer than the timeout in total, as long as each read finishes in time.
+    /// If the request is retried, each attempt gets the full timeout. The timeout can be
+    /// overridden for some calls with `Client::with_timeout()`.
+    ///
+    /// Custom transports receive the timeout in `Transport::get_with_timeout()`, and Hyper
+    /// clients set with `hyper_client()` use their own timeouts instead. By default, there is no
+    /// timeout.
+    pub fn timeout(mut self, timeout: Duration) -> ClientBuilder {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the policy used to retry the requests that fail with a transient error.
+    ///
+    /// By default, the requests are not retried.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> ClientBuilder {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Sets the rate limiter of the requests.
+    ///
+    /// By default, each client gets its own `RateLimiter::default()`, with the default budgets
+    /// for the public and the private methods. `RateLimiter::unlimited()` disables the limits.
+    /// The same limiter can be given to several clients so that they share their request
+    /// budgets.
+    pub fn rate_limiter(mut self, rate_limiter: RateLimiter) -> ClientBuilder {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
 
-    fn deref(&self) -> &hyper::Client {
-        &self.inner
+    /// Sets the maximum length of the pool usernames accepted in new orders, in bytes.
+    ///
+    /// By default, `types::DEFAULT_MAX_POOL_USERNAME_LENGTH` is used.
+    pub fn max_pool_username_length(mut self, max_length: usize) -> ClientBuilder {
+        self.max_pool_username_length = Some(max_length);
+        self
+    }
+
+    /// Builds the API client.
+    ///
+    /// It will also call the API to check that the connection works and to store the remote API
+    /// version.
+    pub fn build(self) -> Result<Client> {
+        let client = self.build_lazy()?;
+        let _ = client.update_api_version()?;
+        Ok(client)
+    }
+
+    /// Builds the API client without connecting to the API.
+    ///
+    /// The remote API version will be retrieved the first time it's requested with
+    /// `Client::get_api_version()`. This will only fail if the API URL is not valid.
+    pub fn build_lazy(self) -> Result<Client> {
+        let api_url = match self.api_url {
+            Some(ref api_url) => Url::parse(api_url)?,
+            None => Url::parse(API_URL)?,
+        };
+        let transport: Arc<Transport> = match self.transport {
+            Some(transport) => transport,
+            None => Arc::new(HttpTransport::new(self.timeout)),
+        };
+        Ok(Client {
+            transport: transport,
+            api_url: api_url,
+            timeout: self.timeout,
+            retry_policy: self.retry_policy,
+            rate_limiter: self.rate_limiter.unwrap_or_default(),
+            max_pool_username_length: self.max_pool_username_length,
+            remote_version: Arc::new(Mutex::new(None)),
+        })
     }
 }
 
 /// Calculates the withdrawal fee for the given amount.
-pub fn calculate_withdrawal_fee(amount: f64) -> f64 {
-    if amount > 0.5 { amount * 0.001 } else { 0.0005 }
+///
+/// It is 0.1% of the amount, with a minimum of 0.0005 BTC for amounts up to 0.5 BTC. It returns
+/// `None` if the amount is negative.
+pub fn calculate_withdrawal_fee(amount: Btc) -> Option<Btc> {
+    if amount.is_negative() {
+        None
+    } else if amount > Btc::from_satoshis(50_000_000) {
+        amount.checked_mul_per_mille(1)
+    } else {
+        Some(Btc::from_satoshis(50_000))
+    }
+}
+
+/// Calculates the service fee for the given order expenditure.
+///
+/// It returns `None` if the amount is negative.
+pub fn calculate_service_fee(amount: Btc) -> Option<Btc> {
+    if amount.is_negative() {
+        None
+    } else {
+        amount.checked_mul_per_mille(SERVICE_FEE_PER_MILLE)
+    }
 }
 
 /// Enum representing the location of the servers.
@@ -112,6 +420,15 @@ pub enum Location {
 }
 
 impl Location {
+    /// Creates a `Location` from a `u64`.
+    fn from_u64(val: u64) -> Result<Location> {
+        match val {
+            0 => Ok(Location::Europe),
+            1 => Ok(Location::USA),
+            l => Err(Error::ParseLocation(format!("invalid location {}", l))),
+        }
+    }
+
     /// Gets an `&str` representing the `u64` of the server location.
     fn as_str(&self) -> &str {
         match *self {
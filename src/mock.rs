@@ -0,0 +1,880 @@
+//! In-process mock of the [nicehash.com](https://www.nicehash.com/) API.
+//!
+//! This module is only available with the `mock` feature. It runs a small HTTP server on a local
+//! port that answers the API methods implemented by the `Client` with a stateful fake order book.
+//! It can be used to test code built on top of the `Client` deterministically, without network
+//! access and without touching real funds.
+
+use std::collections::{BTreeMap, HashMap};
+use std::result::Result as StdResult;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use hyper::Url;
+use hyper::header::ContentType;
+use hyper::server::{Server, Listening, Request, Response};
+use hyper::uri::RequestUri;
+use serde_json::builder::{ArrayBuilder, ObjectBuilder};
+use serde_json::ser;
+use serde_json::value::Value;
+
+use super::{Client, ClientBuilder, RateLimiter, Location, Algorithm, OrderType, Btc,
+            calculate_service_fee};
+use error::Result;
+use types::PoolInfo;
+
+/// Version reported by the mock API.
+const MOCK_API_VERSION: &'static str = "1.2.6";
+
+/// Result of a mock API method, the error is the message returned to the client.
+type MethodResult = StdResult<Value, &'static str>;
+
+/// Mock [nicehash.com](https://www.nicehash.com/) API server.
+///
+/// The server starts listening on a random local port when created, and keeps answering requests
+/// until it is dropped. All the state (accounts, balances and orders) is kept in memory and can be
+/// inspected and modified through the methods of this structure.
+#[derive(Debug)]
+pub struct MockServer {
+    listening: Listening,
+    api_url: String,
+    state: Arc<Mutex<State>>,
+}
+
+impl MockServer {
+    /// Starts a new mock server on a random local port.
+    pub fn start() -> Result<MockServer> {
+        let state = Arc::new(Mutex::new(State::default()));
+        let handler_state = state.clone();
+        let listening = Server::http("127.0.0.1:0")?
+            .handle(move |request: Request, response: Response| {
+                handle(&handler_state, request, response)
+            })?;
+        let api_url = format!("http://{}/api", listening.socket);
+        Ok(MockServer {
+            listening: listening,
+            api_url: api_url,
+            state: state,
+        })
+    }
+
+    /// Gets the base URL of the mock API.
+    pub fn get_api_url(&self) -> &str {
+        &self.api_url
+    }
+
+    /// Creates a new API client connected to this mock server.
+    ///
+    /// The mock server does not throttle the requests, so the client does not limit them either.
+    pub fn client(&self) -> Result<Client> {
+        ClientBuilder::new()
+            .api_url(self.api_url.as_str())
+            .rate_limiter(RateLimiter::unlimited())
+            .build()
+    }
+
+    /// Sets the minimum duration between two consecutive order price reductions.
+    ///
+    /// By default, it is 600 seconds (10 minutes), as in the real API.
+    pub fn set_down_time(&self, down_time: Duration) {
+        self.state.lock().unwrap().down_time = down_time;
+    }
+
+    /// Registers a new account with its full access API key and its read-only API key.
+    ///
+    /// New accounts start with an empty balance.
+    pub fn add_account<K, R>(&self, api_id: u64, api_key: K, read_only_api_key: R)
+        where K: Into<String>,
+              R: Into<String>
+    {
+        let _ = self.state.lock().unwrap().accounts.insert(api_id,
+                                                           Account {
+                                                               api_key: api_key.into(),
+                                                               read_only_api_key:
+                                                                   read_only_api_key.into(),
+                                                               confirmed: Btc::zero(),
+                                                               pending: Btc::zero(),
+                                                           });
+    }
+
+    /// Sets the confirmed and pending balance of the given account.
+    ///
+    /// It does nothing if the account has not been registered.
+    pub fn set_balance(&self, api_id: u64, confirmed: Btc, pending: Btc) {
+        if let Some(account) = self.state.lock().unwrap().accounts.get_mut(&api_id) {
+            account.confirmed = confirmed;
+            account.pending = pending;
+        }
+    }
+
+    /// Adds an order from another buyer to the order book, and returns its ID.
+    pub fn add_order(&self, order: MockOrder) -> u64 {
+        self.state.lock().unwrap().insert_order(order, None, None)
+    }
+
+    /// Gets the order with the given ID, if it exists.
+    pub fn get_order(&self, order_id: u64) -> Option<MockOrder> {
+        self.state.lock().unwrap().orders.get(&order_id).map(|entry| entry.order.clone())
+    }
+
+    /// Gets the pool information of an order created through the API, if it exists.
+    pub fn get_order_pool(&self, order_id: u64) -> Option<PoolInfo> {
+        self.state.lock().unwrap().orders.get(&order_id).and_then(|entry| entry.pool.clone())
+    }
+
+    /// Sets the stats of the given provider address for the algorithm of the stats.
+    ///
+    /// Any previous stats of the provider for the same algorithm are replaced.
+    pub fn set_provider_stat<S: Into<String>>(&self, addr: S, stat: MockProviderStat) {
+        let mut state = self.state.lock().unwrap();
+        let stats = state.providers.entry(addr.into()).or_insert_with(Vec::new);
+        stats.retain(|s| s.algorithm != stat.algorithm);
+        stats.push(stat);
+    }
+
+    /// Adds a payment to the given provider address.
+    pub fn add_provider_payment<S: Into<String>>(&self, addr: S, payment: MockPayment) {
+        self.state
+            .lock()
+            .unwrap()
+            .payments
+            .entry(addr.into())
+            .or_insert_with(Vec::new)
+            .push(payment);
+    }
+
+    /// Adds a worker to the given provider address.
+    pub fn add_provider_worker<S: Into<String>>(&self, addr: S, worker: MockWorker) {
+        self.state
+            .lock()
+            .unwrap()
+            .workers
+            .entry(addr.into())
+            .or_insert_with(Vec::new)
+            .push(worker);
+    }
+
+    /// Modifies the order with the given ID.
+    ///
+    /// This can be used to simulate workers connecting to or leaving an order, or the order
+    /// spending its funds. It returns `false` if the order does not exist.
+    pub fn update_order<F>(&self, order_id: u64, update: F) -> bool
+        where F: FnOnce(&mut MockOrder)
+    {
+        match self.state.lock().unwrap().orders.get_mut(&order_id) {
+            Some(entry) => {
+                update(&mut entry.order);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        let _ = self.listening.close();
+    }
+}
+
+/// Order stored in the order book of the mock server.
+#[derive(Debug, Clone)]
+pub struct MockOrder {
+    /// Location of the order.
+    pub location: Location,
+    /// Algorithm of the order.
+    pub algorithm: Algorithm,
+    /// Type of the order.
+    pub order_type: OrderType,
+    /// Price of the order.
+    pub price: Btc,
+    /// Speed limit of the order, `0.0` meaning no limit.
+    pub limit_speed: f64,
+    /// Current accepted speed of the order.
+    pub accepted_speed: f64,
+    /// Number of workers working for the order.
+    pub workers: u64,
+    /// Whether the order is alive or not.
+    pub alive: bool,
+    /// Remaining BTCs in the order.
+    pub amount: Btc,
+}
+
+impl MockOrder {
+    /// Creates a new alive standard order without workers nor speed limit.
+    pub fn new(location: Location, algorithm: Algorithm, price: Btc) -> MockOrder {
+        MockOrder {
+            location: location,
+            algorithm: algorithm,
+            order_type: OrderType::Standard,
+            price: price,
+            limit_speed: 0.0,
+            accepted_speed: 0.0,
+            workers: 0,
+            alive: true,
+            amount: Btc::zero(),
+        }
+    }
+
+    /// Converts the order to the JSON object returned by the `orders.get` method.
+    ///
+    /// The remaining BTCs are only included for the orders of the account.
+    fn to_json(&self, id: u64, with_funds: bool) -> Value {
+        let order = ObjectBuilder::new()
+            .insert("id", id)
+            .insert("type", self.order_type as u64)
+            .insert("limit_speed", format!("{}", self.limit_speed))
+            .insert("alive", self.alive)
+            .insert("price", self.price.to_string())
+            .insert("workers", self.workers)
+            .insert("algo", self.algorithm as u64)
+            .insert("accepted_speed", format!("{}", self.accepted_speed));
+        if with_funds {
+            order.insert("btc_avail", self.amount.to_string()).build()
+        } else {
+            order.build()
+        }
+    }
+}
+
+/// Stats of a hashing power provider for an algorithm in the mock server.
+#[derive(Debug, Clone)]
+pub struct MockProviderStat {
+    /// Algorithm of the stats.
+    pub algorithm: Algorithm,
+    /// Unpaid balance of the provider, in BTCs.
+    pub balance: Btc,
+    /// Accepted hashing speed.
+    pub accepted_speed: f64,
+    /// Rejected hashing speed.
+    pub rejected_speed: f64,
+    /// Profitability of the algorithm, in BTCs per unit of speed per day.
+    pub profitability: f64,
+}
+
+impl MockProviderStat {
+    /// Converts the stats to the speeds object used in the `stats.provider.ex` method.
+    fn speeds_json(&self) -> Value {
+        ObjectBuilder::new()
+            .insert("a", format!("{}", self.accepted_speed))
+            .insert("rt", format!("{}", self.rejected_speed))
+            .build()
+    }
+}
+
+/// Payment to a hashing power provider in the mock server.
+#[derive(Debug, Clone)]
+pub struct MockPayment {
+    /// Amount paid, in BTCs.
+    pub amount: Btc,
+    /// Fee charged for the payment, in BTCs.
+    pub fee: Btc,
+    /// ID of the Bitcoin transaction of the payment.
+    pub transaction_id: String,
+    /// Time of the payment.
+    pub time: SystemTime,
+}
+
+impl MockPayment {
+    /// Converts the payment to the JSON object returned by the API.
+    fn to_json(&self) -> Value {
+        ObjectBuilder::new()
+            .insert("amount", self.amount.to_string())
+            .insert("fee", self.fee.to_string())
+            .insert("TXID", self.transaction_id.as_str())
+            .insert("time",
+                    self.time.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0))
+            .build()
+    }
+}
+
+/// Worker of a hashing power provider in the mock server.
+#[derive(Debug, Clone)]
+pub struct MockWorker {
+    /// Name of the worker (rig).
+    pub name: String,
+    /// Algorithm the worker is mining with.
+    pub algorithm: Algorithm,
+    /// Accepted hashing speed of the worker.
+    pub accepted_speed: f64,
+    /// Rejected hashing speed of the worker.
+    pub rejected_speed: f64,
+    /// Time the worker has been connected.
+    pub connected_time: Duration,
+    /// Current difficulty of the worker.
+    pub difficulty: f64,
+    /// Location of the server the worker is connected to.
+    pub location: Location,
+}
+
+impl MockWorker {
+    /// Converts the worker to the JSON array returned by the API.
+    fn to_json(&self, with_algorithm: bool) -> Value {
+        let worker = ArrayBuilder::new()
+            .push(self.name.as_str())
+            .push_object(|speeds| {
+                speeds.insert("a", format!("{}", self.accepted_speed))
+                    .insert("rt", format!("{}", self.rejected_speed))
+            })
+            .push(self.connected_time.as_secs() / 60)
+            .push(0)
+            .push(format!("{}", self.difficulty))
+            .push(self.location as u64);
+        if with_algorithm {
+            worker.push(self.algorithm as u64).build()
+        } else {
+            worker.build()
+        }
+    }
+}
+
+/// Account registered in the mock server.
+#[derive(Debug)]
+struct Account {
+    api_key: String,
+    read_only_api_key: String,
+    confirmed: Btc,
+    pending: Btc,
+}
+
+/// Order book entry of the mock server.
+#[derive(Debug)]
+struct Entry {
+    order: MockOrder,
+    owner: Option<u64>,
+    pool: Option<PoolInfo>,
+    last_decrease: Option<Instant>,
+}
+
+/// Internal state of the mock server.
+#[derive(Debug)]
+struct State {
+    down_time: Duration,
+    accounts: HashMap<u64, Account>,
+    orders: BTreeMap<u64, Entry>,
+    next_order_id: u64,
+    providers: HashMap<String, Vec<MockProviderStat>>,
+    payments: HashMap<String, Vec<MockPayment>>,
+    workers: HashMap<String, Vec<MockWorker>>,
+}
+
+impl Default for State {
+    fn default() -> State {
+        State {
+            down_time: Duration::from_secs(600),
+            accounts: HashMap::new(),
+            orders: BTreeMap::new(),
+            next_order_id: 1,
+            providers: HashMap::new(),
+            payments: HashMap::new(),
+            workers: HashMap::new(),
+        }
+    }
+}
+
+impl State {
+    /// Inserts a new order in the order book and returns its ID.
+    fn insert_order(&mut self,
+                    order: MockOrder,
+                    owner: Option<u64>,
+                    pool: Option<PoolInfo>)
+                    -> u64 {
+        let id = self.next_order_id;
+        self.next_order_id += 1;
+        let _ = self.orders.insert(id,
+                                   Entry {
+                                       order: order,
+                                       owner: owner,
+                                       pool: pool,
+                                       last_decrease: None,
+                                   });
+        id
+    }
+
+    /// Answers the request with the given query parameters.
+    fn respond(&mut self, params: &HashMap<String, String>) -> Value {
+        let method = match params.get("method") {
+            Some(method) => method.as_str(),
+            None => {
+                return ObjectBuilder::new()
+                    .insert_object("result",
+                                   |result| result.insert("api_version", MOCK_API_VERSION))
+                    .insert("method", Value::Null)
+                    .build();
+            }
+        };
+        let result = match method {
+            "stats.global.current" => self.global_stats(params, true),
+            "stats.global.24h" => self.global_stats(params, false),
+            "orders.get" if params.contains_key("my") => self.my_orders(params),
+            "orders.get" => self.orders(params),
+            "buy.info" => Ok(self.buy_info()),
+            "orders.create" => self.create_order(params),
+            "orders.refill" => self.refill_order(params),
+            "orders.remove" => self.remove_order(params),
+            "orders.set.price" => self.set_order_price(params),
+            "orders.set.price.decrease" => self.decrease_order_price(params),
+            "orders.set.limit" => self.set_order_limit(params),
+            "balance" => self.balance(params),
+            "stats.provider" => self.provider_stats(params),
+            "stats.provider.ex" => self.provider_stats_ex(params),
+            "stats.provider.payments" => self.provider_payments(params),
+            "stats.provider.workers" => self.provider_workers(params),
+            "multialgo.info" => Ok(self.multialgo_info("multialgo")),
+            "simplemultialgo.info" => Ok(self.multialgo_info("simplemultialgo")),
+            _ => Err("Method not supported."),
+        };
+        let result = match result {
+            Ok(result) => result,
+            Err(e) => ObjectBuilder::new().insert("error", e).build(),
+        };
+        ObjectBuilder::new().insert("result", result).insert("method", method).build()
+    }
+
+    /// Checks the API ID and key of the request, and returns the API ID of the account.
+    fn authenticate(&self,
+                    params: &HashMap<String, String>,
+                    write: bool)
+                    -> StdResult<u64, &'static str> {
+        let api_id = params.get("id").and_then(|id| id.parse().ok()).ok_or("Incorrect key.")?;
+        let api_key = params.get("key").ok_or("Incorrect key.")?;
+        match self.accounts.get(&api_id) {
+            Some(account) if &account.api_key == api_key => Ok(api_id),
+            Some(account) if &account.read_only_api_key == api_key => {
+                if write {
+                    Err("Read-only API key.")
+                } else {
+                    Ok(api_id)
+                }
+            }
+            _ => Err("Incorrect key."),
+        }
+    }
+
+    /// Gets the own order referenced in the request.
+    fn own_order(&mut self,
+                 params: &HashMap<String, String>,
+                 api_id: u64,
+                 error: &'static str)
+                 -> StdResult<&mut Entry, &'static str> {
+        let location = parse_location(params)?;
+        let algorithm = parse_algorithm(params)?;
+        let order_id: u64 = params.get("order").and_then(|id| id.parse().ok()).ok_or(error)?;
+        match self.orders.get_mut(&order_id) {
+            Some(entry) => {
+                if entry.owner == Some(api_id) && entry.order.location == location &&
+                   entry.order.algorithm == algorithm {
+                    Ok(entry)
+                } else {
+                    Err(error)
+                }
+            }
+            None => Err(error),
+        }
+    }
+
+    /// `stats.global.current` and `stats.global.24h` methods.
+    fn global_stats(&self, params: &HashMap<String, String>, current: bool) -> MethodResult {
+        let location = if current && params.contains_key("location") {
+            Some(parse_location(params)?)
+        } else {
+            None
+        };
+        let mut stats = ArrayBuilder::new();
+        for algorithm in Algorithm::all().iter() {
+            let (price, speed) = self.market(*algorithm, location);
+            stats = stats.push_object(|stat| {
+                stat.insert("price", price.to_string())
+                    .insert("speed", format!("{}", speed))
+                    .insert("algo", *algorithm as u64)
+            });
+        }
+        Ok(ObjectBuilder::new().insert("stats", stats.build()).build())
+    }
+
+    /// Gets the average price (weighted by speed), rounded to satoshis, and total speed of the
+    /// alive orders of the given algorithm.
+    fn market(&self, algorithm: Algorithm, location: Option<Location>) -> (Btc, f64) {
+        let (mut speed, mut weighted_price) = (0.0, 0.0);
+        for entry in self.orders.values() {
+            let order = &entry.order;
+            if order.alive && order.algorithm == algorithm &&
+               location.map_or(true, |l| l == order.location) {
+                speed += order.accepted_speed;
+                weighted_price += order.accepted_speed * order.price.to_f64();
+            }
+        }
+        let price = if speed > 0.0 { weighted_price / speed } else { 0.0 };
+        (Btc::from_f64(price).unwrap_or_default(), speed)
+    }
+
+    /// `multialgo.info` and `simplemultialgo.info` methods.
+    ///
+    /// The paying price of each algorithm is the average price of its alive orders.
+    fn multialgo_info(&self, field: &str) -> Value {
+        let mut algorithms = ArrayBuilder::new();
+        for algorithm in Algorithm::all().iter() {
+            let (price, _) = self.market(*algorithm, None);
+            algorithms = algorithms.push_object(|info| {
+                info.insert("paying", price.to_string())
+                    .insert("port", 3333 + *algorithm as u64)
+                    .insert("name", format!("{:?}", algorithm).to_lowercase())
+                    .insert("algo", *algorithm as u64)
+            });
+        }
+        ObjectBuilder::new().insert(field, algorithms.build()).build()
+    }
+
+    /// `orders.get` method.
+    fn orders(&self, params: &HashMap<String, String>) -> MethodResult {
+        let location = parse_location(params)?;
+        let algorithm = parse_algorithm(params)?;
+        let mut orders = ArrayBuilder::new();
+        for (id, entry) in &self.orders {
+            if entry.order.location == location && entry.order.algorithm == algorithm {
+                orders = orders.push(entry.order.to_json(*id, false));
+            }
+        }
+        Ok(ObjectBuilder::new().insert("orders", orders.build()).build())
+    }
+
+    /// `orders.get&my` method.
+    fn my_orders(&self, params: &HashMap<String, String>) -> MethodResult {
+        let api_id = self.authenticate(params, false)?;
+        let location = parse_location(params)?;
+        let algorithm = parse_algorithm(params)?;
+        let mut orders = ArrayBuilder::new();
+        for (id, entry) in &self.orders {
+            if entry.owner == Some(api_id) && entry.order.location == location &&
+               entry.order.algorithm == algorithm {
+                orders = orders.push(entry.order.to_json(*id, true));
+            }
+        }
+        Ok(ObjectBuilder::new().insert("orders", orders.build()).build())
+    }
+
+    /// `buy.info` method.
+    fn buy_info(&self) -> Value {
+        let mut algorithms = ArrayBuilder::new();
+        for algorithm in Algorithm::all().iter() {
+            let (speed_text, down_step) = buy_info_for(*algorithm);
+            algorithms = algorithms.push_object(|info| {
+                info.insert("down_step", down_step)
+                    .insert("min_limit", "0.01")
+                    .insert("speed_text", speed_text)
+                    .insert("multi", "1")
+                    .insert("algo", *algorithm as u64)
+            });
+        }
+        ObjectBuilder::new()
+            .insert("down_time", self.down_time.as_secs())
+            .insert("min_amount", "0.01")
+            .insert("algorithms", algorithms.build())
+            .build()
+    }
+
+    /// `orders.create` method.
+    fn create_order(&mut self, params: &HashMap<String, String>) -> MethodResult {
+        let api_id = self.authenticate(params, true)?;
+        let location = parse_location(params)?;
+        let algorithm = parse_algorithm(params)?;
+        let amount = parse_btc(params, "amount").ok_or("Invalid amount or price.")?;
+        let price = parse_btc(params, "price").ok_or("Invalid amount or price.")?;
+        if amount <= Btc::zero() || price <= Btc::zero() {
+            return Err("Invalid amount or price.");
+        }
+        let limit = parse_f64(params, "limit").ok_or("Invalid limit.")?;
+        if limit < 0.0 {
+            return Err("Invalid limit.");
+        }
+        let pool = PoolInfo {
+            host: params.get("pool_host").cloned().unwrap_or_else(String::new),
+            port: params.get("pool_port").and_then(|port| port.parse().ok()).unwrap_or(0),
+            username: params.get("pool_user").cloned().unwrap_or_else(String::new),
+            password: params.get("pool_pass").cloned().unwrap_or_else(String::new),
+        };
+        if pool.host.is_empty() || pool.port == 0 {
+            return Err("Invalid pool.");
+        }
+        {
+            let account = self.accounts.get_mut(&api_id).ok_or("Incorrect key.")?;
+            if account.confirmed < amount {
+                return Err("Not enough funds.");
+            }
+            account.confirmed -= amount;
+        }
+
+        let mut order = MockOrder::new(location, algorithm, price);
+        order.limit_speed = limit;
+        order.amount = amount;
+        let id = self.insert_order(order, Some(api_id), Some(pool));
+        Ok(ObjectBuilder::new().insert("success", format!("Order {} created.", id)).build())
+    }
+
+    /// `orders.refill` method.
+    ///
+    /// The order gets the given amount, and the account pays it plus the service fee.
+    fn refill_order(&mut self, params: &HashMap<String, String>) -> MethodResult {
+        let api_id = self.authenticate(params, true)?;
+        let amount = parse_btc(params, "amount").ok_or("Invalid amount or order id.")?;
+        if amount <= Btc::zero() {
+            return Err("Invalid amount or order id.");
+        }
+        let cost = calculate_service_fee(amount)
+            .and_then(|fee| amount.checked_add(fee))
+            .ok_or("Invalid amount or order id.")?;
+        if self.accounts.get(&api_id).map_or(true, |account| account.confirmed < cost) {
+            return Err("Not enough funds.");
+        }
+        self.own_order(params, api_id, "Invalid amount or order id.")?.order.amount += amount;
+        if let Some(account) = self.accounts.get_mut(&api_id) {
+            account.confirmed -= cost;
+        }
+        Ok(ObjectBuilder::new().insert("success", "Order refilled.").build())
+    }
+
+    /// `orders.remove` method.
+    fn remove_order(&mut self, params: &HashMap<String, String>) -> MethodResult {
+        let api_id = self.authenticate(params, true)?;
+        let amount = self.own_order(params, api_id, "Unknown order id.")?.order.amount;
+        if let Some(order_id) = params.get("order").and_then(|id| id.parse().ok()) {
+            let _ = self.orders.remove(&order_id);
+        }
+        if let Some(account) = self.accounts.get_mut(&api_id) {
+            account.confirmed += amount;
+        }
+        Ok(ObjectBuilder::new().insert("success", "Order removed.").build())
+    }
+
+    /// `orders.set.price` method.
+    fn set_order_price(&mut self, params: &HashMap<String, String>) -> MethodResult {
+        let api_id = self.authenticate(params, true)?;
+        let price = parse_btc(params, "price").ok_or("Price incorrect.")?;
+        let entry = self.own_order(params, api_id, "Order id incorrect.")?;
+        if price < entry.order.price {
+            return Err("Price can only be increased.");
+        }
+        entry.order.price = price;
+        Ok(ObjectBuilder::new().insert("success", "Order price changed.").build())
+    }
+
+    /// `orders.set.price.decrease` method.
+    fn decrease_order_price(&mut self, params: &HashMap<String, String>) -> MethodResult {
+        let api_id = self.authenticate(params, true)?;
+        let down_time = self.down_time;
+        let entry = self.own_order(params, api_id, "No such order.")?;
+        if entry.last_decrease.map_or(false, |last| last.elapsed() < down_time) {
+            return Err("Price decrease too soon.");
+        }
+        let down_step: Btc = buy_info_for(entry.order.algorithm).1.parse().unwrap_or_default();
+        let price = entry.order.price + down_step;
+        if price <= Btc::zero() {
+            return Err("Price incorrect.");
+        }
+        entry.order.price = price;
+        entry.last_decrease = Some(Instant::now());
+        Ok(ObjectBuilder::new()
+            .insert("success", format!("New order price set to: {:.4}", price.to_f64()))
+            .build())
+    }
+
+    /// `orders.set.limit` method.
+    fn set_order_limit(&mut self, params: &HashMap<String, String>) -> MethodResult {
+        let api_id = self.authenticate(params, true)?;
+        let limit = parse_f64(params, "limit").ok_or("Order id/limit/algo incorrect.")?;
+        if limit < 0.0 {
+            return Err("Order id/limit/algo incorrect.");
+        }
+        let entry = self.own_order(params, api_id, "Order id incorrect.")?;
+        entry.order.limit_speed = limit;
+        Ok(ObjectBuilder::new().insert("success", "Order limit changed.").build())
+    }
+
+    /// `balance` method.
+    fn balance(&self, params: &HashMap<String, String>) -> MethodResult {
+        let api_id = self.authenticate(params, false)?;
+        let account = self.accounts.get(&api_id).ok_or("Incorrect key.")?;
+        Ok(ObjectBuilder::new()
+            .insert("balance_confirmed", account.confirmed.to_string())
+            .insert("balance_pending", account.pending.to_string())
+            .build())
+    }
+
+    /// Gets the stats of the provider address in the request.
+    fn provider(&self,
+                params: &HashMap<String, String>)
+                -> StdResult<&[MockProviderStat], &'static str> {
+        match params.get("addr") {
+            Some(addr) if !addr.is_empty() => {
+                Ok(self.providers.get(addr).map_or(&[], |stats| stats.as_slice()))
+            }
+            _ => Err("Incorrect BTC address."),
+        }
+    }
+
+    /// Gets the payments of the provider address in the request.
+    fn payments_json(&self, params: &HashMap<String, String>) -> Value {
+        let mut payments = ArrayBuilder::new();
+        if let Some(addr_payments) = params.get("addr").and_then(|addr| self.payments.get(addr)) {
+            for payment in addr_payments {
+                payments = payments.push(payment.to_json());
+            }
+        }
+        payments.build()
+    }
+
+    /// `stats.provider` method.
+    fn provider_stats(&self, params: &HashMap<String, String>) -> MethodResult {
+        let mut stats = ArrayBuilder::new();
+        for stat in self.provider(params)? {
+            stats = stats.push_object(|s| {
+                s.insert("balance", stat.balance.to_string())
+                    .insert("rejected_speed", format!("{}", stat.rejected_speed))
+                    .insert("algo", stat.algorithm as u64)
+                    .insert("accepted_speed", format!("{}", stat.accepted_speed))
+            });
+        }
+        Ok(ObjectBuilder::new()
+            .insert("stats", stats.build())
+            .insert("payments", self.payments_json(params))
+            .insert("addr", params["addr"].as_str())
+            .build())
+    }
+
+    /// `stats.provider.payments` method.
+    fn provider_payments(&self, params: &HashMap<String, String>) -> MethodResult {
+        let _ = self.provider(params)?;
+        Ok(ObjectBuilder::new()
+            .insert("payments", self.payments_json(params))
+            .insert("addr", params["addr"].as_str())
+            .build())
+    }
+
+    /// `stats.provider.workers` method.
+    fn provider_workers(&self, params: &HashMap<String, String>) -> MethodResult {
+        let _ = self.provider(params)?;
+        let algorithm = if params.contains_key("algo") {
+            Some(parse_algorithm(params)?)
+        } else {
+            None
+        };
+        let mut workers = ArrayBuilder::new();
+        if let Some(addr_workers) = self.workers.get(&params["addr"]) {
+            for worker in addr_workers {
+                if algorithm.map_or(true, |a| a == worker.algorithm) {
+                    workers = workers.push(worker.to_json(algorithm.is_none()));
+                }
+            }
+        }
+        Ok(ObjectBuilder::new()
+            .insert("addr", params["addr"].as_str())
+            .insert("workers", workers.build())
+            .insert("algo", algorithm.map_or(-1, |a| a as i64))
+            .build())
+    }
+
+    /// `stats.provider.ex` method.
+    ///
+    /// The history contains a single sample per algorithm, taken at the time of the request.
+    fn provider_stats_ex(&self, params: &HashMap<String, String>) -> MethodResult {
+        let stats = self.provider(params)?;
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        let from = params.get("from").and_then(|from| from.parse().ok()).unwrap_or(0);
+        let (mut current, mut past) = (ArrayBuilder::new(), ArrayBuilder::new());
+        for stat in stats {
+            current = current.push_object(|s| {
+                s.insert("algo", stat.algorithm as u64)
+                    .insert("name", format!("{:?}", stat.algorithm))
+                    .insert("suffix", buy_info_for(stat.algorithm).0)
+                    .insert("profitability", format!("{}", stat.profitability))
+                    .insert_array("data", |data| {
+                        data.push(stat.speeds_json()).push(stat.balance.to_string())
+                    })
+            });
+            if from <= now {
+                past = past.push_object(|h| {
+                    h.insert("algo", stat.algorithm as u64)
+                        .insert_array("data", |data| {
+                            data.push_array(|sample| {
+                                sample.push(now / 300)
+                                    .push(stat.speeds_json())
+                                    .push(stat.balance.to_string())
+                            })
+                        })
+                });
+            }
+        }
+        Ok(ObjectBuilder::new()
+            .insert("addr", params["addr"].as_str())
+            .insert("current", current.build())
+            .insert("past", past.build())
+            .insert("payments", self.payments_json(params))
+            .build())
+    }
+}
+
+/// Handles a request to the mock server.
+fn handle(state: &Mutex<State>, request: Request, mut response: Response) {
+    let params = match request.uri {
+        RequestUri::AbsolutePath(ref path) => {
+            match Url::parse(&format!("http://localhost{}", path)) {
+                Ok(url) => {
+                    url.query_pairs()
+                        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+                        .collect()
+                }
+                Err(_) => HashMap::new(),
+            }
+        }
+        _ => HashMap::new(),
+    };
+    let body = ser::to_string(&state.lock().unwrap().respond(&params))
+        .expect("could not serialize mock API response");
+    response.headers_mut().set(ContentType::json());
+    let _ = response.send(body.as_bytes());
+}
+
+/// Parses the `location` parameter of a request.
+fn parse_location(params: &HashMap<String, String>) -> StdResult<Location, &'static str> {
+    match params.get("location").map(|location| location.as_str()) {
+        Some("0") => Ok(Location::Europe),
+        Some("1") => Ok(Location::USA),
+        _ => Err("Incorrect location."),
+    }
+}
+
+/// Parses the `algo` parameter of a request.
+fn parse_algorithm(params: &HashMap<String, String>) -> StdResult<Algorithm, &'static str> {
+    params.get("algo")
+        .and_then(|algo| algo.parse().ok())
+        .and_then(|algo| Algorithm::from_u64(algo).ok())
+        .ok_or("Incorrect algorithm.")
+}
+
+/// Parses a float parameter of a request.
+fn parse_f64(params: &HashMap<String, String>, name: &str) -> Option<f64> {
+    params.get(name).and_then(|value| value.parse().ok())
+}
+
+/// Parses a BTC amount parameter of a request.
+fn parse_btc(params: &HashMap<String, String>, name: &str) -> Option<Btc> {
+    params.get(name).and_then(|value| value.parse().ok())
+}
+
+/// Gets the speed text and the price down step of the given algorithm.
+fn buy_info_for(algorithm: Algorithm) -> (&'static str, &'static str) {
+    match algorithm {
+        Algorithm::SHA256 => ("TH", "-0.0001"),
+        Algorithm::Axiom |
+        Algorithm::ScryptJaneNf16 |
+        Algorithm::Hodl |
+        Algorithm::CryptoNight => ("kH", "-0.0010"),
+        Algorithm::Blake256r8 |
+        Algorithm::Blake256r14 |
+        Algorithm::Blake256r8vnl |
+        Algorithm::Decred |
+        Algorithm::Lbry => ("GH", "-0.0001"),
+        Algorithm::Equihash => ("Sol", "-0.0010"),
+        _ => ("MH", "-0.0010"),
+    }
+}
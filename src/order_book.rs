@@ -0,0 +1,163 @@
+//! Order book analytics.
+//!
+//! The `orders.get` API method returns the orders of an algorithm in a location as a flat list.
+//! `OrderBook` sorts those orders by price and groups them in price levels, to easily find out how
+//! the hashrate of the market is distributed between the orders.
+
+use super::{OrderType, Btc};
+use types::Order;
+
+/// Snapshot of the orders of an algorithm in a location.
+///
+/// Miners work for the highest priced orders first, so the orders are sorted from the highest to
+/// the lowest price.
+#[derive(Debug, Clone)]
+pub struct OrderBook {
+    orders: Vec<Order>,
+}
+
+impl OrderBook {
+    /// Creates a new order book from the list of orders returned by the API.
+    pub fn new(mut orders: Vec<Order>) -> OrderBook {
+        orders.sort_by(|a, b| b.get_price().cmp(&a.get_price()));
+        OrderBook { orders: orders }
+    }
+
+    /// Gets all the orders, sorted from the highest to the lowest price.
+    pub fn get_orders(&self) -> &[Order] {
+        &self.orders
+    }
+
+    /// Gets the orders of the given type, sorted from the highest to the lowest price.
+    pub fn get_orders_by_type(&self, order_type: OrderType) -> Vec<&Order> {
+        self.orders.iter().filter(|order| order.get_order_type() == order_type).collect()
+    }
+
+    /// Creates a new order book with only the orders of the given type.
+    ///
+    /// Fixed price orders get their hashrate regardless of the price of the rest of the orders, so
+    /// the standard order book is usually the relevant one when pricing a standard order.
+    pub fn filter_by_type(&self, order_type: OrderType) -> OrderBook {
+        OrderBook {
+            orders: self.orders
+                .iter()
+                .filter(|order| order.get_order_type() == order_type)
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// Checks if the order book has no orders.
+    pub fn is_empty(&self) -> bool {
+        self.orders.is_empty()
+    }
+
+    /// Gets the total accepted speed of all the orders.
+    pub fn get_total_speed(&self) -> f64 {
+        self.orders.iter().map(|order| order.get_accepted_speed()).sum()
+    }
+
+    /// Gets the price levels of the order book, from the highest to the lowest price.
+    ///
+    /// Each level contains all the orders with the same price, and the cumulative accepted speed
+    /// of all the orders at that price or above it.
+    pub fn get_price_levels(&self) -> Vec<PriceLevel> {
+        let mut levels: Vec<PriceLevel> = Vec::new();
+        let mut cumulative_speed = 0.0;
+        for order in &self.orders {
+            cumulative_speed += order.get_accepted_speed();
+            if let Some(level) = levels.last_mut() {
+                if level.price == order.get_price() {
+                    level.orders += 1;
+                    level.workers += order.get_workers();
+                    level.accepted_speed += order.get_accepted_speed();
+                    level.cumulative_speed = cumulative_speed;
+                    continue;
+                }
+            }
+            levels.push(PriceLevel {
+                price: order.get_price(),
+                orders: 1,
+                workers: order.get_workers(),
+                accepted_speed: order.get_accepted_speed(),
+                cumulative_speed: cumulative_speed,
+            });
+        }
+        levels
+    }
+
+    /// Gets the price that an order has to outbid to win the given share of the current hashrate.
+    ///
+    /// The share must be between 0 and 1. An order priced above the returned price would take the
+    /// hashrate of all the orders at that price or below it, which is at least the given share of
+    /// the total accepted speed. It returns `None` if the order book has no accepted speed.
+    pub fn get_price_for_share(&self, share: f64) -> Option<Btc> {
+        let total_speed = self.get_total_speed();
+        if total_speed <= 0.0 {
+            return None;
+        }
+        let target_speed = total_speed * share.max(0.0).min(1.0);
+
+        let mut outbid_speed = 0.0;
+        for level in self.get_price_levels().iter().rev() {
+            outbid_speed += level.accepted_speed;
+            if outbid_speed >= target_speed && level.accepted_speed > 0.0 {
+                return Some(level.price);
+            }
+        }
+        None
+    }
+
+    /// Gets the lowest price of the alive standard orders that currently have workers.
+    ///
+    /// Standard orders priced below this price are not getting any hashrate. Fixed price orders
+    /// get their hashrate regardless of their price, so they are ignored. It returns `None` if no
+    /// standard order has workers.
+    pub fn get_lowest_working_price(&self) -> Option<Btc> {
+        self.orders
+            .iter()
+            .rev()
+            .find(|order| {
+                order.get_order_type() != OrderType::Fixed && order.is_alive() &&
+                order.get_workers() > 0
+            })
+            .map(|order| order.get_price())
+    }
+}
+
+/// Price level of an order book.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PriceLevel {
+    price: Btc,
+    orders: usize,
+    workers: u64,
+    accepted_speed: f64,
+    cumulative_speed: f64,
+}
+
+impl PriceLevel {
+    /// Gets the price of the level.
+    pub fn get_price(&self) -> Btc {
+        self.price
+    }
+
+    /// Gets the number of orders with this price.
+    pub fn get_orders(&self) -> usize {
+        self.orders
+    }
+
+    /// Gets the number of workers of the orders with this price.
+    pub fn get_workers(&self) -> u64 {
+        self.workers
+    }
+
+    /// Gets the accepted speed of the orders with this price.
+    pub fn get_accepted_speed(&self) -> f64 {
+        self.accepted_speed
+    }
+
+    /// Gets the accepted speed of the orders with this price or above it.
+    pub fn get_cumulative_speed(&self) -> f64 {
+        self.cumulative_speed
+    }
+}
@@ -0,0 +1,257 @@
+//! Automated management of the prices of the orders of an account.
+//!
+//! NiceHash allows raising the price of an order at any moment, but the price can only be
+//! decreased by the price step of the algorithm, and only once every `BuyInfo::get_down_time()`.
+//! The `OrderManager` keeps a set of orders just above the cheapest price that currently gets
+//! hashrate, decreasing their price when they are too expensive and raising it as soon as the
+//! competition outbids them.
+
+use std::cmp;
+use std::collections::BTreeMap;
+use std::time::Instant;
+
+use super::{Location, Algorithm, OrderType, Btc};
+use auth::AuthenticatedClient;
+use error::{Result, Error};
+use hashrate::Hashrate;
+use order_book::OrderBook;
+use pricing::PriceEstimate;
+
+/// Manager that keeps orders at the cheapest price that gets hashrate.
+///
+/// The manager does not run by itself: `update()` has to be called periodically, for example
+/// every minute, and it will check the managed orders and change their prices if needed.
+#[derive(Debug)]
+pub struct OrderManager<'c> {
+    client: AuthenticatedClient<'c>,
+    location: Location,
+    algorithm: Algorithm,
+    max_price: Option<Btc>,
+    orders: BTreeMap<u64, ManagedOrder>,
+}
+
+impl<'c> OrderManager<'c> {
+    /// Creates a new order manager for the orders of the given location and algorithm.
+    ///
+    /// The client must have full access credentials, since the manager modifies the orders.
+    pub fn new(client: AuthenticatedClient<'c>,
+               location: Location,
+               algorithm: Algorithm)
+               -> OrderManager<'c> {
+        OrderManager {
+            client: client,
+            location: location,
+            algorithm: algorithm,
+            max_price: None,
+            orders: BTreeMap::new(),
+        }
+    }
+
+    /// Sets the maximum price of the managed orders.
+    ///
+    /// The manager will never raise the price of an order above it. By default, there is no
+    /// maximum price.
+    pub fn max_price(mut self, max_price: Btc) -> OrderManager<'c> {
+        self.max_price = Some(max_price);
+        self
+    }
+
+    /// Starts managing the order with the given ID.
+    pub fn manage(&mut self, order_id: u64) {
+        let _ = self.orders.entry(order_id).or_insert_with(ManagedOrder::default);
+    }
+
+    /// Stops managing the order with the given ID.
+    ///
+    /// It returns `false` if the order was not managed.
+    pub fn unmanage(&mut self, order_id: u64) -> bool {
+        self.orders.remove(&order_id).is_some()
+    }
+
+    /// Gets the IDs of the managed orders.
+    pub fn get_managed_orders(&self) -> Vec<u64> {
+        self.orders.keys().cloned().collect()
+    }
+
+    /// Gets the last time the manager decreased the price of the given order, if any.
+    pub fn get_last_decrease(&self, order_id: u64) -> Option<Instant> {
+        self.orders.get(&order_id).and_then(|order| order.last_decrease)
+    }
+
+    /// Gets the maximum price of the managed orders, if any.
+    pub fn get_max_price(&self) -> Option<Btc> {
+        self.max_price
+    }
+
+    /// Checks the managed orders and updates their prices.
+    ///
+    /// The target price is one price step above the lowest price of the standard orders of other
+    /// accounts that have workers. For each managed order:
+    ///
+    /// - If its price is below the target price, or if it had workers in the last update but it
+    ///   lost them, the price is raised immediately, up to the maximum price.
+    /// - If its price is at least one step above the target price, it is decreased by one step,
+    ///   unless the last decrease was less than `BuyInfo::get_down_time()` ago.
+    ///
+    /// Orders that no longer exist or are dead stop being managed. The buy information is
+    /// retrieved in every update, so that changes in the price step or the down time are taken
+    /// into account. It fails if the buy information, the orders or the order book cannot be
+    /// retrieved. If a price change fails, the update stops and the changes performed until then
+    /// are returned along with the error.
+    pub fn update(&mut self) -> Result<PriceUpdate> {
+        let buy_info = self.client.get_client().get_buy_info()?;
+        let my_orders = self.client.get_my_orders(self.location, self.algorithm)?;
+        let order_book = self.client.get_client().get_order_book(self.location, self.algorithm)?;
+
+        let alive = my_orders.iter()
+            .filter(|order| order.is_alive())
+            .map(|order| order.get_id())
+            .collect::<Vec<_>>();
+        let removed = self.orders
+            .keys()
+            .filter(|id| !alive.contains(id))
+            .cloned()
+            .collect::<Vec<_>>();
+        for id in removed {
+            let _ = self.orders.remove(&id);
+        }
+
+        let (target_price, step, down_time) = {
+            let algo_buy_info = buy_info.get_buy_info_for(self.algorithm);
+            let competitors = OrderBook::new(order_book.get_orders()
+                .iter()
+                .filter(|order| {
+                    order.get_order_type() == OrderType::Standard &&
+                    my_orders.iter().all(|my_order| my_order.get_id() != order.get_id())
+                })
+                .cloned()
+                .collect());
+            let estimate = PriceEstimate::new(self.algorithm,
+                                              &competitors,
+                                              algo_buy_info,
+                                              Hashrate::from_native(0.0, algo_buy_info)?)?;
+            (estimate.get_price(), algo_buy_info.get_down_step().abs(), buy_info.get_down_time())
+        };
+
+        let mut changes = Vec::new();
+        for order in my_orders.iter().filter(|order| order.is_alive()) {
+            let managed = match self.orders.get_mut(&order.get_id()) {
+                Some(managed) => managed,
+                None => continue,
+            };
+
+            let price = order.get_price();
+            let has_workers = order.get_workers() > 0;
+            let lost_workers = managed.had_workers && !has_workers;
+            managed.had_workers = has_workers;
+
+            let mut new_price = target_price;
+            if lost_workers && new_price <= price {
+                new_price = price + step;
+            }
+            if let Some(max_price) = self.max_price {
+                new_price = cmp::min(new_price, max_price);
+            }
+
+            if new_price > price {
+                if let Err(e) = self.client
+                    .set_order_price(self.location, self.algorithm, order.get_id(), new_price) {
+                    return Ok(PriceUpdate {
+                        changes: changes,
+                        error: Some(e),
+                    });
+                }
+                changes.push(PriceChange {
+                    order_id: order.get_id(),
+                    old_price: price,
+                    new_price: new_price,
+                });
+            } else if price - step >= target_price &&
+                      managed.last_decrease.map_or(true, |last| last.elapsed() >= down_time) {
+                match self.client
+                    .decrease_order_price(self.location, self.algorithm, order.get_id()) {
+                    Ok(decreased_price) => {
+                        managed.last_decrease = Some(Instant::now());
+                        changes.push(PriceChange {
+                            order_id: order.get_id(),
+                            old_price: price,
+                            new_price: decreased_price,
+                        });
+                    }
+                    // The price was decreased outside of the manager, at an unknown time in the
+                    // last down time. Waiting a full down time from now avoids retrying in every
+                    // update until the API accepts the decrease.
+                    Err(Error::PriceDecreaseTooSoon(_)) => {
+                        managed.last_decrease = Some(Instant::now());
+                    }
+                    Err(e) => {
+                        return Ok(PriceUpdate {
+                            changes: changes,
+                            error: Some(e),
+                        })
+                    }
+                }
+            }
+        }
+        Ok(PriceUpdate {
+            changes: changes,
+            error: None,
+        })
+    }
+}
+
+/// Result of an `OrderManager::update()`.
+#[derive(Debug)]
+pub struct PriceUpdate {
+    changes: Vec<PriceChange>,
+    error: Option<Error>,
+}
+
+impl PriceUpdate {
+    /// Gets the price changes performed.
+    pub fn get_changes(&self) -> &[PriceChange] {
+        &self.changes
+    }
+
+    /// Gets the error of the price change that stopped the update, if any.
+    pub fn get_error(&self) -> Option<&Error> {
+        self.error.as_ref()
+    }
+}
+
+/// State of a managed order.
+#[derive(Debug, Clone, Copy, Default)]
+struct ManagedOrder {
+    last_decrease: Option<Instant>,
+    had_workers: bool,
+}
+
+/// Price change of an order performed by the `OrderManager`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PriceChange {
+    order_id: u64,
+    old_price: Btc,
+    new_price: Btc,
+}
+
+impl PriceChange {
+    /// Gets the ID of the order.
+    pub fn get_order_id(&self) -> u64 {
+        self.order_id
+    }
+
+    /// Gets the price of the order before the change.
+    pub fn get_old_price(&self) -> Btc {
+        self.old_price
+    }
+
+    /// Gets the price of the order after the change.
+    pub fn get_new_price(&self) -> Btc {
+        self.new_price
+    }
+
+    /// Checks if the price was raised.
+    pub fn is_increase(&self) -> bool {
+        self.new_price > self.old_price
+    }
+}
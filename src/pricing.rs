@@ -0,0 +1,146 @@
+//! Market price estimation for new orders.
+//!
+//! Miners work for the highest priced standard orders first, so an order only gets hashrate if
+//! its price is above the price of the cheapest alive order that currently has workers. To get a
+//! given speed, the order also has to outbid enough orders to take their hashrate. The estimator
+//! combines both rules with the price step of the algorithm to suggest the price of a new order.
+
+use std::cmp;
+
+use super::{Algorithm, OrderType, Btc};
+use error::{Result, Error};
+use hashrate::Hashrate;
+use order_book::OrderBook;
+use types::{AlgoBuyInfo, NewOrder};
+
+/// Price step used if the buy information of the algorithm has no price step, in satoshis.
+const DEFAULT_PRICE_STEP: i64 = 10_000;
+
+/// Estimated competitive price for a new order.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PriceEstimate {
+    algorithm: Algorithm,
+    price: Btc,
+    limit: Hashrate,
+    lowest_working_price: Option<Btc>,
+    outbid_speed: f64,
+}
+
+impl PriceEstimate {
+    /// Estimates the price of a new order of the given algorithm to get the given speed.
+    ///
+    /// Only the standard orders of the order book are taken into account, since fixed price
+    /// orders get their hashrate regardless of the price. The price will be one price step above
+    /// both the lowest working price and the price of the orders that have to be outbid to take
+    /// the target speed from them. If the target speed is above the speed of the whole market,
+    /// the price will be one step above the highest priced order.
+    ///
+    /// The buy information must be the one of the algorithm. It fails if the target speed cannot
+    /// be converted to the native unit of the algorithm.
+    pub fn new(algorithm: Algorithm,
+               order_book: &OrderBook,
+               buy_info: &AlgoBuyInfo,
+               target_speed: Hashrate)
+               -> Result<PriceEstimate> {
+        let target_speed = target_speed.to_native(buy_info)?;
+        let order_book = order_book.filter_by_type(OrderType::Standard);
+        let step = if buy_info.get_down_step().is_zero() {
+            Btc::from_satoshis(DEFAULT_PRICE_STEP)
+        } else {
+            buy_info.get_down_step().abs()
+        };
+        let lowest_working_price = order_book.get_lowest_working_price();
+
+        let levels = order_book.get_price_levels();
+        let mut outbid_speed = 0.0;
+        let mut outbid_price = None;
+        for level in levels.iter().rev() {
+            if outbid_speed >= target_speed {
+                break;
+            }
+            outbid_speed += level.get_accepted_speed();
+            outbid_price = Some(level.get_price());
+        }
+
+        let base_price = match (lowest_working_price, outbid_price) {
+            (Some(working), Some(outbid)) => Some(cmp::max(working, outbid)),
+            (working, outbid) => working.or(outbid),
+        };
+        let price = match base_price {
+            Some(base_price) => {
+                base_price.checked_add(step)
+                    .and_then(|price| round_up_to_step(price, step))
+                    .ok_or_else(|| {
+                        Error::Overflow(format!("the price one step of {} BTC above {} BTC \
+                                                 overflows",
+                                                step,
+                                                base_price))
+                    })?
+            }
+            None => step,
+        };
+
+        Ok(PriceEstimate {
+            algorithm: algorithm,
+            price: price,
+            limit: Hashrate::from_native(target_speed.max(buy_info.get_min_limit()), buy_info)?,
+            lowest_working_price: lowest_working_price,
+            outbid_speed: outbid_speed,
+        })
+    }
+
+    /// Gets the algorithm of the estimate.
+    pub fn get_algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    /// Gets the suggested price for the new order.
+    pub fn get_price(&self) -> Btc {
+        self.price
+    }
+
+    /// Gets the suggested speed limit for the new order.
+    ///
+    /// It is the target speed, or the minimum limit of the algorithm if the target speed is
+    /// below it, in the native unit of the algorithm.
+    pub fn get_limit(&self) -> Hashrate {
+        self.limit
+    }
+
+    /// Gets the lowest price of the standard orders that currently have workers, if any.
+    pub fn get_lowest_working_price(&self) -> Option<Btc> {
+        self.lowest_working_price
+    }
+
+    /// Gets the current accepted speed of the orders outbid by the suggested price, in the native
+    /// unit of the algorithm.
+    ///
+    /// If it's below the target speed, the market does not currently have enough hashrate for the
+    /// target speed.
+    pub fn get_outbid_speed(&self) -> f64 {
+        self.outbid_speed
+    }
+
+    /// Creates a new order with the suggested price and limit, paid with the given amount.
+    pub fn to_new_order(&self, amount: Btc) -> NewOrder {
+        NewOrder {
+            algorithm: self.algorithm,
+            amount: amount,
+            price: self.price,
+            limit: Some(self.limit),
+        }
+    }
+}
+
+/// Rounds the given price up to a multiple of the price step, or `None` if it overflows.
+///
+/// The step must be positive.
+fn round_up_to_step(price: Btc, step: Btc) -> Option<Btc> {
+    let (price, step) = (price.get_satoshis(), step.get_satoshis());
+    let remainder = price % step;
+    if remainder > 0 {
+        (price - remainder).checked_add(step).map(Btc::from_satoshis)
+    } else {
+        Some(Btc::from_satoshis(price - remainder))
+    }
+}
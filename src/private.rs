@@ -1,12 +1,12 @@
 //! Private API methods.
 
-use hyper::Url;
-use serde_json::de;
 use serde_json::value::Value;
 
-use super::{Client, API_URL, Location, Algorithm};
+use super::{Client, Location, Algorithm, Btc};
 use error::{Result, Error};
-use types::{Order, PoolInfo, NewOrder, Balance};
+use hashrate::{Hashrate, HashrateUnit};
+use response::ApiResponse;
+use types::{Order, PoolInfo, NewOrder, Balance, BuyInfo};
 
 /// Private API methods.
 impl Client {
@@ -17,7 +17,7 @@ impl Client {
                                         location: Location,
                                         algorithm: Algorithm)
                                         -> Result<Vec<Order>> {
-        let mut url = Url::parse(API_URL).unwrap();
+        let mut url = self.api_url.clone();
         {
             let mut query_pairs = url.query_pairs_mut();
             let _ = query_pairs.append_pair("method", "orders.get");
@@ -28,64 +28,16 @@ impl Client {
             let _ = query_pairs.append_pair("algo", algorithm.as_str());
         }
 
-        let response = self.inner.get(url).send()?;
-        if let Value::Object(r) = de::from_reader(response)? {
-            for (key, value) in r {
-                if let ("result", Value::Object(s)) = (key.as_str(), value) {
-                    if s.is_empty() {
-                        return Err(Error::Api("empty result response to `orders.get&my`"
-                            .to_owned()));
-                    }
-                    for (key, value) in s {
-                        match key.as_str() {
-                            "orders" => {
-                                if let Value::Array(arr) = value {
-                                    let mut orders = Vec::with_capacity(arr.len());
-                                    for order_json in arr {
-                                        match Order::from_json(order_json) {
-                                            Ok(o) => orders.push(o),
-                                            Err(e) => return Err(e),
-                                        }
-                                    }
-                                    return Ok(orders);
-                                } else {
-                                    return Err(Error::Api("invalid `orders` field found in \
-                                                           `orders.get&my` response, expected \
-                                                           array"
-                                        .to_owned()));
-                                }
-                            }
-                            "error" => {
-                                if let Value::String(s) = value {
-                                    return Err(Error::Result(s));
-                                } else {
-                                    return Err(Error::Api("invalid `error` field found in \
-                                                           `orders.get&my` response, expected \
-                                                           string"
-                                        .to_owned()));
-                                }
-                            }
-                            f => {
-                                return Err(Error::Api(format!("unknown field `{}` found in \
-                                                               `orders.get&my` response",
-                                                              f)));
-                            }
-                        }
-                    }
-                    return Err(Error::Api("invalid response to `orders.get&my` method, no \
-                                           `orders` field was found"
-                        .to_owned()));
-                }
-            }
-            Err(Error::Api("invalid response to `orders.get&my` method, no `result` field was \
-                            found"
-                .to_owned()))
-        } else {
-            Err(Error::Api("invalid response to `orders.get&my` method".to_owned()))
-        }
+        self.private_call(url, ApiResponse::new("orders.get&my").field("orders"), true)
     }
 
     /// Creates a new order.
+    ///
+    /// The pool is checked with `PoolInfo::validate()` and the maximum username length of the
+    /// client before creating the order, but the order is not checked against the buy
+    /// information. If the limit is not in the native unit of the algorithm, the buy information
+    /// is retrieved to convert it. Use `create_order_checked()` to check the order with buy
+    /// information that was already retrieved.
     pub fn create_order<K: AsRef<str>>(&self,
                                        api_id: u64,
                                        api_key: K,
@@ -94,10 +46,12 @@ impl Client {
                                        pool: PoolInfo,
                                        code: Option<u32>)
                                        -> Result<u64> {
-        if order.amount < 0.00000001 || order.price < 0.00000001 {
-            return Err(Error::Result("Invalid amount or price.".to_owned()));
+        if order.amount < Btc::from_satoshis(1) || order.price < Btc::from_satoshis(1) {
+            return Err(Error::from_result("Invalid amount or price."));
         }
-        let mut url = Url::parse(API_URL).unwrap();
+        pool.validate(self.max_pool_username_length)?;
+        let limit = self.limit_param(order.algorithm, order.limit)?;
+        let mut url = self.api_url.clone();
         {
             let mut query_pairs = url.query_pairs_mut();
             let _ = query_pairs.append_pair("method", "orders.create");
@@ -105,13 +59,9 @@ impl Client {
             let _ = query_pairs.append_pair("key", api_key.as_ref());
             let _ = query_pairs.append_pair("location", location.as_str());
             let _ = query_pairs.append_pair("algo", order.algorithm.as_str());
-            let _ = query_pairs.append_pair("amount", &format!("{:.8}", order.amount));
+            let _ = query_pairs.append_pair("amount", &format!("{}", order.amount));
             let _ = query_pairs.append_pair("price", &format!("{}", order.price));
-            if let Some(limit) = order.limit {
-                let _ = query_pairs.append_pair("limit", &format!("{}", limit));
-            } else {
-                let _ = query_pairs.append_pair("limit", "0");
-            }
+            let _ = query_pairs.append_pair("limit", &limit);
             let _ = query_pairs.append_pair("pool_host", &pool.host);
             let _ = query_pairs.append_pair("pool_port", &format!("{}", pool.port));
             let _ = query_pairs.append_pair("pool_user", &pool.username);
@@ -121,64 +71,48 @@ impl Client {
             }
         }
 
-        let response = self.inner.get(url).send()?;
-        if let Value::Object(r) = de::from_reader(response)? {
-            for (key, value) in r {
-                if let ("result", Value::Object(s)) = (key.as_str(), value) {
-                    if s.is_empty() {
-                        return Err(Error::Api("empty result response to `orders.create`"
-                            .to_owned()));
-                    }
-                    for (key, value) in s {
-                        match key.as_str() {
-                            "success" => {
-                                if let Value::String(message) = value {
-                                    let (_, id_plus) = message.split_at(6);
-                                    let (id, _) = id_plus.split_at(id_plus.find(' ')
-                                        .ok_or_else(|| {
-                                            Error::Api("unexpected success string in \
-                                                        orders.create response (it must have a \
-                                                        space after the order ID)"
-                                                .to_owned())
-                                        })?);
-                                    return Ok(id.parse()?);
-                                } else {
-                                    return Err(Error::Api("invalid `success` field found in \
-                                                           `orders.create` response, expected \
-                                                          string"
-                                        .to_owned()));
-                                }
-                            }
-                            "error" => {
-                                if let Value::String(s) = value {
-                                    return Err(Error::Result(s));
-                                } else {
-                                    return Err(Error::Api("invalid `error` field found in \
-                                                           `orders.create` response, expected \
-                                                           string"
-                                        .to_owned()));
-                                }
-                            }
-                            f => {
-                                return Err(Error::Api(format!("unknown field `{}` found in \
-                                                               `orders.create` response",
-                                                              f)));
-                            }
-                        }
-                    }
-                    return Err(Error::Api("invalid response to `orders.create` method, no \
-                                           `success` or `error` fields were found"
-                        .to_owned()));
+        match self.private_call(url, ApiResponse::new("orders.create").field("success"), false)? {
+            Value::String(message) => {
+                let invalid = || {
+                    Error::Api(format!("unexpected success string `{}` in orders.create \
+                                        response, expected `Order <id> created.`",
+                                       message))
+                };
+                let prefix = "Order ";
+                if !message.starts_with(prefix) {
+                    return Err(invalid());
                 }
+                let id_plus = &message[prefix.len()..];
+                let id = &id_plus[..id_plus.find(' ').ok_or_else(&invalid)?];
+                id.parse().map_err(|_| invalid())
+            }
+            _ => {
+                Err(Error::Api("invalid `success` field found in `orders.create` response, \
+                                expected string"
+                    .to_owned()))
             }
-            Err(Error::Api("invalid response to `orders.create` method, no `result` field was \
-                            found"
-                .to_owned()))
-        } else {
-            Err(Error::Api("invalid response to `orders.create` method".to_owned()))
         }
     }
 
+    /// Creates a new order, after checking it with `NewOrder::validate()` and the given buy
+    /// information.
+    ///
+    /// The limit of the order is converted to the native unit of the algorithm with the given
+    /// buy information, so no other API call is needed to create the order.
+    pub fn create_order_checked<K: AsRef<str>>(&self,
+                                               api_id: u64,
+                                               api_key: K,
+                                               location: Location,
+                                               order: NewOrder,
+                                               pool: PoolInfo,
+                                               code: Option<u32>,
+                                               buy_info: &BuyInfo)
+                                               -> Result<u64> {
+        order.validate(buy_info)?;
+        let order = order.with_native_limit(buy_info)?;
+        self.create_order(api_id, api_key, location, order, pool, code)
+    }
+
     /// Refills the given order with the given amount.
     pub fn refill_order<K: AsRef<str>>(&self,
                                        api_id: u64,
@@ -186,12 +120,12 @@ impl Client {
                                        location: Location,
                                        algorithm: Algorithm,
                                        order_id: u64,
-                                       amount: f64)
+                                       amount: Btc)
                                        -> Result<()> {
-        if order_id == 0 || amount < 0.00000001 {
-            return Err(Error::Result("Invalid amount or order id.".to_owned()));
+        if order_id == 0 || amount < Btc::from_satoshis(1) {
+            return Err(Error::from_result("Invalid amount or order id."));
         }
-        let mut url = Url::parse(API_URL).unwrap();
+        let mut url = self.api_url.clone();
         {
             let mut query_pairs = url.query_pairs_mut();
             let _ = query_pairs.append_pair("method", "orders.refill");
@@ -200,50 +134,10 @@ impl Client {
             let _ = query_pairs.append_pair("location", location.as_str());
             let _ = query_pairs.append_pair("algo", algorithm.as_str());
             let _ = query_pairs.append_pair("order", &format!("{}", order_id));
-            let _ = query_pairs.append_pair("amount", &format!("{:.8}", amount));
+            let _ = query_pairs.append_pair("amount", &format!("{}", amount));
         }
 
-        let response = self.inner.get(url).send()?;
-        if let Value::Object(r) = de::from_reader(response)? {
-            for (key, value) in r {
-                if let ("result", Value::Object(s)) = (key.as_str(), value) {
-                    if s.is_empty() {
-                        return Err(Error::Api("empty result response to `orders.refill`"
-                            .to_owned()));
-                    }
-                    for (key, value) in s {
-                        match key.as_str() {
-                            "success" => {
-                                return Ok(());
-                            }
-                            "error" => {
-                                if let Value::String(s) = value {
-                                    return Err(Error::Result(s));
-                                } else {
-                                    return Err(Error::Api("invalid `error` field found in \
-                                                           `orders.refill` response, expected \
-                                                           string"
-                                        .to_owned()));
-                                }
-                            }
-                            f => {
-                                return Err(Error::Api(format!("unknown field `{}` found in \
-                                                               `orders.refill` response",
-                                                              f)));
-                            }
-                        }
-                    }
-                    return Err(Error::Api("invalid response to `orders.refill` method, no \
-                                           `success` or `error` fields were found"
-                        .to_owned()));
-                }
-            }
-            Err(Error::Api("invalid response to `orders.refill` method, no `result` field was \
-                            found"
-                .to_owned()))
-        } else {
-            Err(Error::Api("invalid response to `orders.refill` method".to_owned()))
-        }
+        self.private_call(url, ApiResponse::new("orders.refill").field("success"), false)
     }
 
     /// Removes the given order.
@@ -255,9 +149,9 @@ impl Client {
                                        order_id: u64)
                                        -> Result<()> {
         if order_id == 0 {
-            return Err(Error::Result("Unknown order id.".to_owned()));
+            return Err(Error::from_result("Unknown order id."));
         }
-        let mut url = Url::parse(API_URL).unwrap();
+        let mut url = self.api_url.clone();
         {
             let mut query_pairs = url.query_pairs_mut();
             let _ = query_pairs.append_pair("method", "orders.remove");
@@ -268,47 +162,7 @@ impl Client {
             let _ = query_pairs.append_pair("order", &format!("{}", order_id));
         }
 
-        let response = self.inner.get(url).send()?;
-        if let Value::Object(r) = de::from_reader(response)? {
-            for (key, value) in r {
-                if let ("result", Value::Object(s)) = (key.as_str(), value) {
-                    if s.is_empty() {
-                        return Err(Error::Api("empty result response to `orders.remove`"
-                            .to_owned()));
-                    }
-                    for (key, value) in s {
-                        match key.as_str() {
-                            "success" => {
-                                return Ok(());
-                            }
-                            "error" => {
-                                if let Value::String(s) = value {
-                                    return Err(Error::Result(s));
-                                } else {
-                                    return Err(Error::Api("invalid `error` field found in \
-                                                           `orders.remove` response, expected \
-                                                           string"
-                                        .to_owned()));
-                                }
-                            }
-                            f => {
-                                return Err(Error::Api(format!("unknown field `{}` found in \
-                                                               `orders.remove` response",
-                                                              f)));
-                            }
-                        }
-                    }
-                    return Err(Error::Api("invalid response to `orders.remove` method, no \
-                                           `success` or `error` fields were found"
-                        .to_owned()));
-                }
-            }
-            Err(Error::Api("invalid response to `orders.remove` method, no `result` field was \
-                            found"
-                .to_owned()))
-        } else {
-            Err(Error::Api("invalid response to `orders.remove` method".to_owned()))
-        }
+        self.private_call(url, ApiResponse::new("orders.remove").field("success"), false)
     }
 
     /// Sets the price to the given order.
@@ -318,15 +172,15 @@ impl Client {
                                           location: Location,
                                           algorithm: Algorithm,
                                           order_id: u64,
-                                          price: f64)
+                                          price: Btc)
                                           -> Result<()> {
         if order_id == 0 {
-            return Err(Error::Result("Order id/price/algo incorrect.".to_owned()));
+            return Err(Error::from_result("Order id/price/algo incorrect."));
         }
-        if price < 0.00000001 {
-            return Err(Error::Result("Price incorrect.".to_owned()));
+        if price < Btc::from_satoshis(1) {
+            return Err(Error::from_result("Price incorrect."));
         }
-        let mut url = Url::parse(API_URL).unwrap();
+        let mut url = self.api_url.clone();
         {
             let mut query_pairs = url.query_pairs_mut();
             let _ = query_pairs.append_pair("method", "orders.set.price");
@@ -335,50 +189,10 @@ impl Client {
             let _ = query_pairs.append_pair("location", location.as_str());
             let _ = query_pairs.append_pair("algo", algorithm.as_str());
             let _ = query_pairs.append_pair("order", &format!("{}", order_id));
-            let _ = query_pairs.append_pair("price", &format!("{:.8}", price));
+            let _ = query_pairs.append_pair("price", &format!("{}", price));
         }
 
-        let response = self.inner.get(url).send()?;
-        if let Value::Object(r) = de::from_reader(response)? {
-            for (key, value) in r {
-                if let ("result", Value::Object(s)) = (key.as_str(), value) {
-                    if s.is_empty() {
-                        return Err(Error::Api("empty result response to `orders.set.price`"
-                            .to_owned()));
-                    }
-                    for (key, value) in s {
-                        match key.as_str() {
-                            "success" => {
-                                return Ok(());
-                            }
-                            "error" => {
-                                if let Value::String(s) = value {
-                                    return Err(Error::Result(s));
-                                } else {
-                                    return Err(Error::Api("invalid `error` field found in \
-                                                           `orders.set.price` response, \
-                                                           expected string"
-                                        .to_owned()));
-                                }
-                            }
-                            f => {
-                                return Err(Error::Api(format!("unknown field `{}` found in \
-                                                               `orders.set.price` response",
-                                                              f)));
-                            }
-                        }
-                    }
-                    return Err(Error::Api("invalid response to `orders.set.price` method, no \
-                                           `success` or `error` fields were found"
-                        .to_owned()));
-                }
-            }
-            Err(Error::Api("invalid response to `orders.set.price` method, no `result` field was \
-                            found"
-                .to_owned()))
-        } else {
-            Err(Error::Api("invalid response to `orders.set.price` method".to_owned()))
-        }
+        self.private_call(url, ApiResponse::new("orders.set.price").field("success"), false)
     }
 
     /// Decrease the price to the given order.
@@ -393,11 +207,11 @@ impl Client {
                                                location: Location,
                                                algorithm: Algorithm,
                                                order_id: u64)
-                                               -> Result<f64> {
+                                               -> Result<Btc> {
         if order_id == 0 {
-            return Err(Error::Result("Order id/price/algo incorrect.".to_owned()));
+            return Err(Error::from_result("Order id/price/algo incorrect."));
         }
-        let mut url = Url::parse(API_URL).unwrap();
+        let mut url = self.api_url.clone();
         {
             let mut query_pairs = url.query_pairs_mut();
             let _ = query_pairs.append_pair("method", "orders.set.price.decrease");
@@ -408,75 +222,53 @@ impl Client {
             let _ = query_pairs.append_pair("order", &format!("{}", order_id));
         }
 
-        let response = self.inner.get(url).send()?;
-        if let Value::Object(r) = de::from_reader(response)? {
-            for (key, value) in r {
-                if let ("result", Value::Object(s)) = (key.as_str(), value) {
-                    if s.is_empty() {
-                        return Err(Error::Api("empty result response to \
-                                               `orders.set.price.decrease`"
-                            .to_owned()));
-                    }
-                    for (key, value) in s {
-                        match key.as_str() {
-                            "success" => {
-                                if let Value::String(s) = value {
-                                    let (_, price) = s.split_at(24);
-                                    return Ok(price.parse()?);
-                                } else {
-                                    return Err(Error::Api("invalid `success` field found in \
-                                                           `orders.set.price.decrease` \
-                                                           response, expected string"
-                                        .to_owned()));
-                                }
-                            }
-                            "error" => {
-                                if let Value::String(s) = value {
-                                    return Err(Error::Result(s));
-                                } else {
-                                    return Err(Error::Api("invalid `error` field found in \
-                                                           `orders.set.price.decrease` \
-                                                           response, expected string"
-                                        .to_owned()));
-                                }
-                            }
-                            f => {
-                                return Err(Error::Api(format!("unknown field `{}` found in \
-                                                               `orders.set.price.decrease` \
-                                                               response",
-                                                              f)));
-                            }
-                        }
-                    }
-                    return Err(Error::Api("invalid response to `orders.set.price.decrease` \
-                                           method, no `success` or `error` fields were found"
-                        .to_owned()));
+        match self.private_call(url,
+                                ApiResponse::new("orders.set.price.decrease").field("success"),
+                                false)? {
+            Value::String(message) => {
+                let invalid = || {
+                    Error::Api(format!("unexpected success string `{}` in \
+                                        orders.set.price.decrease response, expected `New order \
+                                        price set to: <price>`",
+                                       message))
+                };
+                let prefix = "New order price set to: ";
+                if !message.starts_with(prefix) {
+                    return Err(invalid());
                 }
+                message[prefix.len()..].parse().map_err(|_| invalid())
+            }
+            _ => {
+                Err(Error::Api("invalid `success` field found in `orders.set.price.decrease` \
+                                response, expected string"
+                    .to_owned()))
             }
-            Err(Error::Api("invalid response to `orders.set.price.decrease` method, no `result` \
-                            field was found"
-                .to_owned()))
-        } else {
-            Err(Error::Api("invalid response to `orders.set.price.decrease` method".to_owned()))
         }
     }
 
     /// Sets the speed limit for the given order.
     ///
     /// The decrease can only be done each 600 seconds (10 minutes). This number might change in
-    /// the future and can be obtained with the `get_buy_info()` call.
+    /// the future and can be obtained with the `get_buy_info()` call. If the limit is not in the
+    /// native unit of the algorithm, the buy information is also retrieved to convert it.
     pub fn set_order_speed_limit<K: AsRef<str>>(&self,
                                                 api_id: u64,
                                                 api_key: K,
                                                 location: Location,
                                                 algorithm: Algorithm,
                                                 order_id: u64,
-                                                speed_limit: Option<f64>)
+                                                speed_limit: Option<Hashrate>)
                                                 -> Result<()> {
         if order_id == 0 {
-            return Err(Error::Result("Order id/limit/algo incorrect.".to_owned()));
+            return Err(Error::from_result("Order id/limit/algo incorrect."));
         }
-        let mut url = Url::parse(API_URL).unwrap();
+        let speed_limit = match speed_limit {
+            Some(limit) if limit.get_value() <= 0.0 => {
+                return Err(Error::from_result("Order id/limit/algo incorrect."));
+            }
+            limit => self.limit_param(algorithm, limit)?,
+        };
+        let mut url = self.api_url.clone();
         {
             let mut query_pairs = url.query_pairs_mut();
             let _ = query_pairs.append_pair("method", "orders.set.limit");
@@ -485,65 +277,15 @@ impl Client {
             let _ = query_pairs.append_pair("location", location.as_str());
             let _ = query_pairs.append_pair("algo", algorithm.as_str());
             let _ = query_pairs.append_pair("order", &format!("{}", order_id));
-            if let Some(l) = speed_limit {
-                if l <= 0.0 {
-                    return Err(Error::Result("Order id/limit/algo incorrect.".to_owned()));
-                }
-                let _ = query_pairs.append_pair("limit", &format!("{:.8}", l));
-            } else {
-                let _ = query_pairs.append_pair("limit", "0");
-            }
-
+            let _ = query_pairs.append_pair("limit", &speed_limit);
         }
 
-        let response = self.inner.get(url).send()?;
-        if let Value::Object(r) = de::from_reader(response)? {
-            for (key, value) in r {
-                if let ("result", Value::Object(s)) = (key.as_str(), value) {
-                    if s.is_empty() {
-                        return Err(Error::Api("empty result response to \
-                                               `orders.set.price.decrease`"
-                            .to_owned()));
-                    }
-                    for (key, value) in s {
-                        match key.as_str() {
-                            "success" => {
-                                return Ok(());
-                            }
-                            "error" => {
-                                if let Value::String(s) = value {
-                                    return Err(Error::Result(s));
-                                } else {
-                                    return Err(Error::Api("invalid `error` field found in \
-                                                           `orders.set.price.decrease` \
-                                                           response, expected string"
-                                        .to_owned()));
-                                }
-                            }
-                            f => {
-                                return Err(Error::Api(format!("unknown field `{}` found in \
-                                                               `orders.set.price.decrease` \
-                                                               response",
-                                                              f)));
-                            }
-                        }
-                    }
-                    return Err(Error::Api("invalid response to `orders.set.price.decrease` \
-                                           method, no `success` or `error` fields were found"
-                        .to_owned()));
-                }
-            }
-            Err(Error::Api("invalid response to `orders.set.price.decrease` method, no `result` \
-                            field was found"
-                .to_owned()))
-        } else {
-            Err(Error::Api("invalid response to `orders.set.price.decrease` method".to_owned()))
-        }
+        self.private_call(url, ApiResponse::new("orders.set.limit").field("success"), false)
     }
 
     /// Gets the balance of the given account.
     pub fn get_balance<K: AsRef<str>>(&self, api_id: u64, api_key: K) -> Result<Balance> {
-        let mut url = Url::parse(API_URL).unwrap();
+        let mut url = self.api_url.clone();
         {
             let mut query_pairs = url.query_pairs_mut();
             let _ = query_pairs.append_pair("method", "balance");
@@ -551,59 +293,19 @@ impl Client {
             let _ = query_pairs.append_pair("key", api_key.as_ref());
         }
 
-        let response = self.inner.get(url).send()?;
-        if let Value::Object(r) = de::from_reader(response)? {
-            for (key, value) in r {
-                if let ("result", Value::Object(s)) = (key.as_str(), value) {
-                    if s.is_empty() {
-                        return Err(Error::Api("empty result response to `balance`".to_owned()));
-                    }
-                    let mut balance = Balance::default();
-                    for (key, value) in s {
-                        match key.as_str() {
-                            "balance_confirmed" => {
-                                if let Value::String(confirmed) = value {
-                                    balance.confirmed = confirmed.parse()?;
-                                } else {
-                                    return Err(Error::Api("invalid `balance_confirmed` field \
-                                                           found in `balance` response, \
-                                                           expected float in a string"
-                                        .to_owned()));
-                                }
-                            }
-                            "balance_pending" => {
-                                if let Value::String(pending) = value {
-                                    balance.pending = pending.parse()?;
-                                } else {
-                                    return Err(Error::Api("invalid `balance_pending` field \
-                                                           found in `balance` response, \
-                                                           expected float in a string"
-                                        .to_owned()));
-                                }
-                            }
-                            "error" => {
-                                if let Value::String(s) = value {
-                                    return Err(Error::Result(s));
-                                } else {
-                                    return Err(Error::Api("invalid `error` field found in \
-                                                           `balance` response, expected string"
-                                        .to_owned()));
-                                }
-                            }
-                            f => {
-                                return Err(Error::Api(format!("unknown field `{}` found in \
-                                                               `balance` response",
-                                                              f)));
-                            }
-                        }
-                    }
-                    return Ok(balance);
-                }
-            }
-            Err(Error::Api("invalid response to `balance` method, no `result` field was found"
-                .to_owned()))
-        } else {
-            Err(Error::Api("invalid response to `balance` method".to_owned()))
-        }
+        self.private_call(url, ApiResponse::new("balance"), true)
+    }
+
+    /// Gets the `limit` parameter of the API for the given speed limit, in the native unit of
+    /// the algorithm, or `0` if there is no limit.
+    ///
+    /// The buy information is only retrieved if the limit is not in the native unit.
+    fn limit_param(&self, algorithm: Algorithm, limit: Option<Hashrate>) -> Result<String> {
+        let limit = match limit {
+            Some(limit) if limit.get_unit() == HashrateUnit::Native => limit.get_value(),
+            Some(limit) => limit.to_native(self.get_buy_info()?.get_buy_info_for(algorithm))?,
+            None => 0.0,
+        };
+        Ok(format!("{}", limit))
     }
 }
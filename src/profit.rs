@@ -0,0 +1,169 @@
+//! Profitability of selling hashing power.
+//!
+//! The global stats of the API show the price paid for each algorithm, in BTC per unit of speed
+//! per day. `ProfitAdvisor` combines those prices with the hashrates of a rig to find out which
+//! algorithm pays best for it.
+
+use std::cmp::Ordering;
+
+use super::{Algorithm, Btc, calculate_withdrawal_fee};
+use error::{Result, Error};
+use hashrate::Hashrate;
+use types::{GlobalStats, BuyInfo};
+
+/// Advisor to choose the most profitable algorithm for a rig.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProfitAdvisor {
+    hashrates: Vec<(Algorithm, Hashrate)>,
+    withdrawal_days: f64,
+}
+
+impl ProfitAdvisor {
+    /// Creates a new profit advisor without hashrates.
+    ///
+    /// By default, the earnings are expected to be withdrawn once a day.
+    pub fn new() -> ProfitAdvisor {
+        ProfitAdvisor {
+            hashrates: Vec::new(),
+            withdrawal_days: 1.0,
+        }
+    }
+
+    /// Sets the hashrate of the rig for the given algorithm.
+    ///
+    /// The hashrate can be in any unit compatible with the algorithm, it will be converted to the
+    /// native unit of the algorithm when calculating the profit.
+    pub fn hashrate(mut self, algorithm: Algorithm, hashrate: Hashrate) -> ProfitAdvisor {
+        self.hashrates.retain(|&(a, _)| a != algorithm);
+        self.hashrates.push((algorithm, hashrate));
+        self
+    }
+
+    /// Sets the number of days between withdrawals of the earnings.
+    ///
+    /// The withdrawal fee is paid in each withdrawal, so withdrawing less often reduces its
+    /// impact on small earnings.
+    pub fn withdrawal_days(mut self, days: f64) -> ProfitAdvisor {
+        self.withdrawal_days = days;
+        self
+    }
+
+    /// Gets the hashrate of the rig for the given algorithm, if any.
+    pub fn get_hashrate(&self, algorithm: Algorithm) -> Option<Hashrate> {
+        self.hashrates.iter().find(|&&(a, _)| a == algorithm).map(|&(_, hashrate)| hashrate)
+    }
+
+    /// Gets the number of days between withdrawals of the earnings.
+    pub fn get_withdrawal_days(&self) -> f64 {
+        self.withdrawal_days
+    }
+
+    /// Calculates the expected profit for the given algorithm.
+    ///
+    /// The hashrate is converted to the native unit of the algorithm and multiplied by
+    /// `AlgoBuyInfo::get_multiplier()` to get it in the units of the prices of the global stats.
+    /// The withdrawal fee of the earnings of each withdrawal period is spread over the days of the
+    /// period. It returns `None` if the rig has no hashrate for the algorithm, and it fails if the
+    /// hashrate cannot be converted to the native unit of the algorithm or if the earnings of a
+    /// withdrawal period are not a valid BTC amount.
+    pub fn profit_for(&self,
+                      algorithm: Algorithm,
+                      stats: &GlobalStats,
+                      buy_info: &BuyInfo)
+                      -> Result<Option<AlgorithmProfit>> {
+        let hashrate = match self.get_hashrate(algorithm) {
+            Some(hashrate) => hashrate,
+            None => return Ok(None),
+        };
+        let algo_buy_info = buy_info.get_buy_info_for(algorithm);
+        let native_hashrate = hashrate.to_native(algo_buy_info)?;
+        let price = stats.get_stats_for(algorithm).get_price();
+        let btc_per_day = native_hashrate * algo_buy_info.get_multiplier() * price;
+        let withdrawal_fee_per_day = if self.withdrawal_days > 0.0 {
+            let withdrawal = Btc::from_f64(btc_per_day * self.withdrawal_days)
+                .ok_or_else(|| {
+                    Error::Overflow(format!("earnings of {} BTC per day for {:?} are too big for \
+                                             a withdrawal",
+                                            btc_per_day,
+                                            algorithm))
+                })?;
+            let withdrawal_fee = calculate_withdrawal_fee(withdrawal)
+                .ok_or_else(|| {
+                    Error::Api(format!("negative earnings of {} BTC per day for {:?}",
+                                       btc_per_day,
+                                       algorithm))
+                })?;
+            withdrawal_fee.to_f64() / self.withdrawal_days
+        } else {
+            0.0
+        };
+        Ok(Some(AlgorithmProfit {
+            algorithm: algorithm,
+            hashrate: hashrate,
+            price: price,
+            btc_per_day: btc_per_day,
+            net_btc_per_day: btc_per_day - withdrawal_fee_per_day,
+        }))
+    }
+
+    /// Ranks the algorithms of the rig by their expected net earnings, from the best paying one.
+    ///
+    /// It fails if any of the hashrates cannot be converted to the native unit of its algorithm.
+    pub fn rank(&self, stats: &GlobalStats, buy_info: &BuyInfo) -> Result<Vec<AlgorithmProfit>> {
+        let mut profits = Vec::with_capacity(self.hashrates.len());
+        for &(algorithm, _) in &self.hashrates {
+            if let Some(profit) = self.profit_for(algorithm, stats, buy_info)? {
+                profits.push(profit);
+            }
+        }
+        profits.sort_by(|a, b| {
+            b.net_btc_per_day.partial_cmp(&a.net_btc_per_day).unwrap_or(Ordering::Equal)
+        });
+        Ok(profits)
+    }
+}
+
+impl Default for ProfitAdvisor {
+    fn default() -> ProfitAdvisor {
+        ProfitAdvisor::new()
+    }
+}
+
+/// Expected profit of a rig for an algorithm.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AlgorithmProfit {
+    algorithm: Algorithm,
+    hashrate: Hashrate,
+    price: f64,
+    btc_per_day: f64,
+    net_btc_per_day: f64,
+}
+
+impl AlgorithmProfit {
+    /// Gets the algorithm.
+    pub fn get_algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    /// Gets the hashrate of the rig for the algorithm, as given to the advisor.
+    pub fn get_hashrate(&self) -> Hashrate {
+        self.hashrate
+    }
+
+    /// Gets the price paid for the algorithm, as shown in the global stats.
+    pub fn get_price(&self) -> f64 {
+        self.price
+    }
+
+    /// Gets the expected earnings, in BTC per day.
+    ///
+    /// It is an estimate from the current price, so it is not rounded to satoshis.
+    pub fn get_btc_per_day(&self) -> f64 {
+        self.btc_per_day
+    }
+
+    /// Gets the expected earnings after the withdrawal fee, in BTC per day.
+    pub fn get_net_btc_per_day(&self) -> f64 {
+        self.net_btc_per_day
+    }
+}
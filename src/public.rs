@@ -1,18 +1,25 @@
 //! Public API methods.
 
-use hyper::Url;
-use serde_json::de;
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::collections::BTreeMap;
+
 use serde_json::value::Value;
 
-use super::{Client, API_URL, Location, Algorithm};
+use super::{Client, Location, Algorithm};
 use error::{Result, Error};
-use types::{GlobalStats, Order, BuyInfo};
+use hashrate::Hashrate;
+use response::ApiResponse;
+use order_book::OrderBook;
+use pricing::PriceEstimate;
+use profit::{ProfitAdvisor, AlgorithmProfit};
+use types::{GlobalStats, Order, BuyInfo, ProviderStats, ProviderStatsEx, Payment,
+            ProviderWorker, MultiAlgoInfo};
 
 /// Public API methods.
 impl Client {
     /// Gets current global stats.
     pub fn global_stats_current(&self, location: Option<Location>) -> Result<GlobalStats> {
-        let mut url = Url::parse(API_URL).unwrap();
+        let mut url = self.api_url.clone();
         {
             let mut query_pairs = url.query_pairs_mut();
             let _ = query_pairs.append_pair("method", "stats.global.current");
@@ -21,94 +28,23 @@ impl Client {
             }
         }
 
-        let response = self.inner.get(url).send()?;
-        if let Value::Object(r) = de::from_reader(response)? {
-            for (key, value) in r {
-                if let ("result", Value::Object(s)) = (key.as_str(), value) {
-                    if s.is_empty() {
-                        return Err(Error::Api("empty result response to `stats.global.current`"
-                            .to_owned()));
-                    }
-                    for (key, value) in s {
-                        match key.as_str() {
-                            "stats" => {
-                                if let Value::Array(arr) = value {
-                                    return GlobalStats::from_json(arr);
-                                } else {
-                                    return Err(Error::Api("invalid `stats` field found in \
-                                                           `stats.global.current` response, \
-                                                           expected array"
-                                        .to_owned()));
-                                }
-                            }
-                            "error" => {
-                                if let Value::String(s) = value {
-                                    return Err(Error::Result(s));
-                                } else {
-                                    return Err(Error::Api("invalid `error` field found in \
-                                                           `stats.global.current` response, \
-                                                           expected string"
-                                        .to_owned()));
-                                }
-                            }
-                            f => {
-                                return Err(Error::Api(format!("unknown field `{}` found in \
-                                                               `stats.global.current` response",
-                                                              f)));
-                            }
-                        }
-                    }
-                    return Err(Error::Api("invalid response to `stats.global.current` \
-                                           method, no `stats` field was found"
-                        .to_owned()));
-                }
-            }
-            Err(Error::Api("invalid response to `stats.global.current` method, no `result` \
-                            field was found"
-                .to_owned()))
-        } else {
-            Err(Error::Api("invalid response to `stats.global.current` method".to_owned()))
-        }
+        self.public_call(url, ApiResponse::new("stats.global.current").field("stats"))
     }
 
     /// Gets global stats for the last 24h.
     pub fn global_stats_24h(&self) -> Result<GlobalStats> {
-        let mut url = Url::parse(API_URL).unwrap();
+        let mut url = self.api_url.clone();
         {
             let mut query_pairs = url.query_pairs_mut();
             let _ = query_pairs.append_pair("method", "stats.global.24h");
         }
 
-        let response = self.inner.get(url).send()?;
-        if let Value::Object(r) = de::from_reader(response)? {
-            for (key, value) in r {
-                if let ("result", Value::Object(s)) = (key.as_str(), value) {
-                    if let Some((key, Value::Array(arr))) = s.into_iter().next() {
-                        if key.as_str() == "stats" {
-                            return GlobalStats::from_json(arr);
-                        } else {
-                            return Err(Error::Api("invalid response to `stats.global.24` \
-                                                   method, no `stats` field was found"
-                                .to_owned()));
-                        }
-                    } else {
-                        return Err(Error::Api("invalid response to `stats.global.24` \
-                                               method, no `stats` field was found"
-                            .to_owned()));
-                    }
-                }
-            }
-            Err(Error::Api("invalid response to `stats.global.24` method, no `result` \
-                            field was found"
-                .to_owned()))
-        } else {
-            Err(Error::Api("invalid response to `stats.global.24` method".to_owned()))
-        }
+        self.public_call(url, ApiResponse::new("stats.global.24h").field("stats"))
     }
 
     /// Gets all orders for the given algorithm and location.
     pub fn get_orders(&self, location: Location, algorithm: Algorithm) -> Result<Vec<Order>> {
-        let mut url = Url::parse(API_URL).unwrap();
+        let mut url = self.api_url.clone();
         {
             let mut query_pairs = url.query_pairs_mut();
             let _ = query_pairs.append_pair("method", "orders.get");
@@ -116,58 +52,162 @@ impl Client {
             let _ = query_pairs.append_pair("algo", algorithm.as_str());
         }
 
-        let response = self.inner.get(url).send()?;
-        if let Value::Object(r) = de::from_reader(response)? {
-            for (key, value) in r {
-                if let ("result", Value::Object(s)) = (key.as_str(), value) {
-                    if let Some((key, Value::Array(arr))) = s.into_iter().next() {
-                        if key.as_str() == "orders" {
-                            let mut orders = Vec::with_capacity(arr.len());
-                            for order_json in arr {
-                                match Order::from_json(order_json) {
-                                    Ok(o) => orders.push(o),
-                                    Err(e) => return Err(e),
-                                }
-                            }
-                            return Ok(orders);
-                        } else {
-                            return Err(Error::Api("invalid response to `orders.get` method, no \
-                                                   `orders` field was found"
-                                .to_owned()));
-                        }
-                    } else {
-                        return Err(Error::Api("invalid response to `orders.get` method, no \
-                                               `orders` field was found"
-                            .to_owned()));
-                    }
-                }
-            }
-            Err(Error::Api("invalid response to `orders.get` method, no `result` field was found"
-                .to_owned()))
-        } else {
-            Err(Error::Api("invalid response to `orders.get` method".to_owned()))
-        }
+        self.public_call(url, ApiResponse::new("orders.get").field("orders"))
+    }
+
+    /// Gets the order book for the given algorithm and location.
+    pub fn get_order_book(&self, location: Location, algorithm: Algorithm) -> Result<OrderBook> {
+        Ok(OrderBook::new(self.get_orders(location, algorithm)?))
+    }
+
+    /// Estimates a competitive price for a new order of the given algorithm and location, to get
+    /// the given target speed.
+    ///
+    /// It gets the current order book and the buy information of the algorithm, and computes the
+    /// estimate as explained in `PriceEstimate::new()`.
+    pub fn estimate_price(&self,
+                          location: Location,
+                          algorithm: Algorithm,
+                          target_speed: Hashrate)
+                          -> Result<PriceEstimate> {
+        let order_book = self.get_order_book(location, algorithm)?;
+        let buy_info = self.get_buy_info()?;
+        PriceEstimate::new(algorithm,
+                           &order_book,
+                           buy_info.get_buy_info_for(algorithm),
+                           target_speed)
+    }
+
+    /// Ranks the algorithms of a rig by their expected earnings with the current prices.
+    ///
+    /// The prices are taken from the current global stats of the given location, or of all the
+    /// locations if `None`.
+    pub fn rank_algorithms(&self,
+                           location: Option<Location>,
+                           advisor: &ProfitAdvisor)
+                           -> Result<Vec<AlgorithmProfit>> {
+        let stats = self.global_stats_current(location)?;
+        let buy_info = self.get_buy_info()?;
+        advisor.rank(&stats, &buy_info)
     }
 
     /// Gets needed information for buying hashing power.
     pub fn get_buy_info(&self) -> Result<BuyInfo> {
-        let mut url = Url::parse(API_URL).unwrap();
+        let mut url = self.api_url.clone();
         {
             let mut query_pairs = url.query_pairs_mut();
             let _ = query_pairs.append_pair("method", "buy.info");
         }
 
-        let response = self.inner.get(url).send()?;
-        if let Value::Object(r) = de::from_reader(response)? {
-            for (key, value) in r {
-                if let ("result", Value::Object(res)) = (key.as_str(), value) {
-                    return BuyInfo::from_json(res);
-                }
+        self.public_call(url, ApiResponse::new("buy.info"))
+    }
+
+    /// Gets current stats for the given provider address.
+    pub fn provider_stats<S: AsRef<str>>(&self, addr: S) -> Result<ProviderStats> {
+        let mut url = self.api_url.clone();
+        {
+            let mut query_pairs = url.query_pairs_mut();
+            let _ = query_pairs.append_pair("method", "stats.provider");
+            let _ = query_pairs.append_pair("addr", addr.as_ref());
+        }
+
+        self.public_call(url,
+                         ApiResponse::new("stats.provider")
+                             .field("stats")
+                             .ignore(&["addr", "payments"]))
+    }
+
+    /// Gets detailed stats for the given provider address.
+    ///
+    /// The historical stats will start at the given time. If no time is given, the API will
+    /// return its default history window.
+    pub fn provider_stats_ex<S: AsRef<str>>(&self,
+                                            addr: S,
+                                            from: Option<SystemTime>)
+                                            -> Result<ProviderStatsEx> {
+        let mut url = self.api_url.clone();
+        {
+            let mut query_pairs = url.query_pairs_mut();
+            let _ = query_pairs.append_pair("method", "stats.provider.ex");
+            let _ = query_pairs.append_pair("addr", addr.as_ref());
+            if let Some(from) = from {
+                let from = from.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+                let _ = query_pairs.append_pair("from", &format!("{}", from));
+            }
+        }
+
+        self.public_call(url, ApiResponse::new("stats.provider.ex"))
+    }
+
+    /// Gets the payments made to the given provider address.
+    pub fn provider_payments<S: AsRef<str>>(&self, addr: S) -> Result<Vec<Payment>> {
+        let mut url = self.api_url.clone();
+        {
+            let mut query_pairs = url.query_pairs_mut();
+            let _ = query_pairs.append_pair("method", "stats.provider.payments");
+            let _ = query_pairs.append_pair("addr", addr.as_ref());
+        }
+
+        self.public_call(url,
+                         ApiResponse::new("stats.provider.payments")
+                             .field("payments")
+                             .ignore(&["addr"]))
+    }
+
+    /// Gets the workers of the given provider address.
+    ///
+    /// If an algorithm is given, only the workers mining with that algorithm will be returned.
+    pub fn provider_workers<S: AsRef<str>>(&self,
+                                           addr: S,
+                                           algorithm: Option<Algorithm>)
+                                           -> Result<Vec<ProviderWorker>> {
+        let mut url = self.api_url.clone();
+        {
+            let mut query_pairs = url.query_pairs_mut();
+            let _ = query_pairs.append_pair("method", "stats.provider.workers");
+            let _ = query_pairs.append_pair("addr", addr.as_ref());
+            if let Some(algorithm) = algorithm {
+                let _ = query_pairs.append_pair("algo", algorithm.as_str());
             }
-            Err(Error::Api("invalid response to `buy.info` method, no `result` field was found"
-                .to_owned()))
+        }
+
+        let mut result: BTreeMap<String, Value> =
+            self.public_call(url, ApiResponse::new("stats.provider.workers"))?;
+        let algorithm = match result.get("algo").and_then(|algo| algo.as_i64()) {
+            Some(algo) if algo >= 0 => Some(Algorithm::from_u64(algo as u64)?),
+            _ => None,
+        };
+        if let Some(Value::Array(arr)) = result.remove("workers") {
+            let mut workers = Vec::with_capacity(arr.len());
+            for worker_json in arr {
+                workers.push(ProviderWorker::from_json(worker_json, algorithm)?);
+            }
+            Ok(workers)
         } else {
-            Err(Error::Api("invalid response to `buy.info` method".to_owned()))
+            Err(Error::Api("invalid response to `stats.provider.workers` method, no `workers` \
+                            field was found"
+                .to_owned()))
         }
     }
+
+    /// Gets the paying prices and ports for multi-algorithm mining.
+    pub fn multialgo_info(&self) -> Result<MultiAlgoInfo> {
+        self.multialgo("multialgo.info", "multialgo")
+    }
+
+    /// Gets the paying prices and ports for simple multi-algorithm mining.
+    pub fn simple_multialgo_info(&self) -> Result<MultiAlgoInfo> {
+        self.multialgo("simplemultialgo.info", "simplemultialgo")
+    }
+
+    /// Calls one of the multi-algorithm information methods.
+    fn multialgo(&self, method: &str, field: &str) -> Result<MultiAlgoInfo> {
+        let mut url = self.api_url.clone();
+        {
+            let mut query_pairs = url.query_pairs_mut();
+            let _ = query_pairs.append_pair("method", method);
+        }
+
+        self.public_call(url, ApiResponse::new(method).field(field))
+    }
 }
@@ -0,0 +1,169 @@
+//! Client-side rate limiting of the API requests.
+
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Maximum number of requests that can be sent to the API in a period of time.
+///
+/// The limit is enforced with a token bucket: up to `requests` requests can be sent at once, and
+/// after that, new requests are allowed at a steady rate of `requests` per `period`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    requests: u32,
+    period: Duration,
+}
+
+impl RateLimit {
+    /// Creates a new rate limit of the given number of requests per period.
+    ///
+    /// At least one request per period will be allowed.
+    pub fn new(requests: u32, period: Duration) -> RateLimit {
+        RateLimit {
+            requests: requests.max(1),
+            period: period,
+        }
+    }
+
+    /// Gets the number of requests allowed in each period.
+    pub fn get_requests(&self) -> u32 {
+        self.requests
+    }
+
+    /// Gets the period of the rate limit.
+    pub fn get_period(&self) -> Duration {
+        self.period
+    }
+}
+
+/// Rate limiter of the API requests of a client.
+///
+/// Public and private API methods have separate budgets, so that polling the public order book
+/// does not delay the management of the orders of the account. When the budget of a request is
+/// exhausted, the client blocks the current thread until a new request is allowed.
+///
+/// The limiter can be cloned cheaply, and all the clones share the same budgets. Clones of a
+/// `Client` share its limiter, and the same limiter can be given to several clients with
+/// `ClientBuilder::rate_limiter()` so that they share their budgets too.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    public: Arc<Mutex<Bucket>>,
+    private: Arc<Mutex<Bucket>>,
+}
+
+impl RateLimiter {
+    /// Creates a new rate limiter with the given limits for the public and the private methods.
+    pub fn new(public: RateLimit, private: RateLimit) -> RateLimiter {
+        RateLimiter {
+            public: Arc::new(Mutex::new(Bucket::new(Some(public)))),
+            private: Arc::new(Mutex::new(Bucket::new(Some(private)))),
+        }
+    }
+
+    /// Creates a rate limiter that does not limit the requests.
+    pub fn unlimited() -> RateLimiter {
+        RateLimiter {
+            public: Arc::new(Mutex::new(Bucket::new(None))),
+            private: Arc::new(Mutex::new(Bucket::new(None))),
+        }
+    }
+
+    /// Gets the rate limit of the public methods, if any.
+    pub fn get_public_limit(&self) -> Option<RateLimit> {
+        self.public.lock().unwrap().limit
+    }
+
+    /// Gets the rate limit of the private methods, if any.
+    pub fn get_private_limit(&self) -> Option<RateLimit> {
+        self.private.lock().unwrap().limit
+    }
+}
+
+/// Default rate limiter.
+///
+/// It allows 60 requests per minute to the public methods and 30 requests per minute to the
+/// private methods, so that polling loops stay well below the rate at which NiceHash throttles
+/// its callers. Clients get this limiter unless another one is set with
+/// `ClientBuilder::rate_limiter()`, and `RateLimiter::unlimited()` can be used to opt out of the
+/// limits.
+impl Default for RateLimiter {
+    fn default() -> RateLimiter {
+        RateLimiter::new(RateLimit::new(60, Duration::from_secs(60)),
+                         RateLimit::new(30, Duration::from_secs(60)))
+    }
+}
+
+/// Waits until a public API request is allowed by the given limiter.
+pub fn acquire_public(limiter: &RateLimiter) {
+    acquire(&limiter.public)
+}
+
+/// Waits until a private API request is allowed by the given limiter.
+pub fn acquire_private(limiter: &RateLimiter) {
+    acquire(&limiter.private)
+}
+
+/// Waits until the given bucket has a token, and takes it.
+///
+/// The lock is released while sleeping, so that other threads can check the bucket too.
+fn acquire(bucket: &Mutex<Bucket>) {
+    loop {
+        let wait = bucket.lock().unwrap().take();
+        match wait {
+            Some(wait) => thread::sleep(wait),
+            None => return,
+        }
+    }
+}
+
+/// Token bucket of a rate limit.
+#[derive(Debug)]
+struct Bucket {
+    limit: Option<RateLimit>,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl Bucket {
+    /// Creates a new full bucket for the given limit.
+    fn new(limit: Option<RateLimit>) -> Bucket {
+        Bucket {
+            limit: limit,
+            tokens: limit.map_or(0.0, |limit| limit.requests as f64),
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Takes a token from the bucket.
+    ///
+    /// If the bucket is empty, it returns the time to wait until the next token is available.
+    fn take(&mut self) -> Option<Duration> {
+        let limit = match self.limit {
+            Some(limit) => limit,
+            None => return None,
+        };
+        let capacity = limit.requests as f64;
+        let rate = capacity / duration_to_secs(limit.period).max(1e-9);
+
+        let now = Instant::now();
+        self.tokens = (self.tokens + duration_to_secs(now - self.last_refill) * rate).min(capacity);
+        self.last_refill = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            None
+        } else {
+            Some(secs_to_duration((1.0 - self.tokens) / rate))
+        }
+    }
+}
+
+/// Converts a duration to seconds.
+fn duration_to_secs(duration: Duration) -> f64 {
+    duration.as_secs() as f64 + duration.subsec_nanos() as f64 / 1_000_000_000.0
+}
+
+/// Converts seconds to a duration.
+fn secs_to_duration(secs: f64) -> Duration {
+    Duration::new(secs.trunc() as u64, (secs.fract() * 1_000_000_000.0) as u32)
+}
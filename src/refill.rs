@@ -0,0 +1,233 @@
+//! Automatic refill of the orders of an account.
+//!
+//! Orders stop when they spend all their BTCs. `AutoRefill` checks the remaining funds of the
+//! orders of an account and refills them before they run out, as long as the account balance and
+//! a daily spending cap allow it.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use super::{Location, Algorithm, Btc, ORDER_FEE, DAY, calculate_service_fee,
+            calculate_withdrawal_fee};
+use auth::AuthenticatedClient;
+use error::{Result, Error};
+
+/// Policy for the automatic refill of orders.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RefillPolicy {
+    threshold: Btc,
+    amount: Btc,
+    daily_cap: Option<Btc>,
+    reserve: Btc,
+}
+
+impl RefillPolicy {
+    /// Creates a new refill policy.
+    ///
+    /// Orders will be refilled when their remaining BTCs drop below the `threshold`, so that they
+    /// get `amount` more BTCs to spend in hashing power. The service fee is added to the refilled
+    /// amount, so each refill costs `amount + calculate_service_fee(amount)`. The amount must
+    /// not be negative.
+    pub fn new(threshold: Btc, amount: Btc) -> RefillPolicy {
+        RefillPolicy {
+            threshold: threshold,
+            amount: amount,
+            daily_cap: None,
+            reserve: Btc::zero(),
+        }
+    }
+
+    /// Sets the maximum BTCs that can be spent in refills in the last 24 hours, fees included.
+    ///
+    /// By default, there is no cap.
+    pub fn daily_cap(mut self, daily_cap: Btc) -> RefillPolicy {
+        self.daily_cap = Some(daily_cap);
+        self
+    }
+
+    /// Sets the confirmed balance that will always be kept in the account.
+    ///
+    /// On top of it, the account will always keep the order fee and the withdrawal fee of the
+    /// remaining balance, so that it's always possible to create a new order or to withdraw the
+    /// balance. By default, no additional balance is reserved.
+    pub fn reserve(mut self, reserve: Btc) -> RefillPolicy {
+        self.reserve = reserve;
+        self
+    }
+
+    /// Gets the remaining BTCs below which orders are refilled.
+    pub fn get_threshold(&self) -> Btc {
+        self.threshold
+    }
+
+    /// Gets the BTCs added to the hashing funds of an order in each refill.
+    pub fn get_amount(&self) -> Btc {
+        self.amount
+    }
+
+    /// Gets the maximum BTCs that can be spent in refills in 24 hours, if any.
+    pub fn get_daily_cap(&self) -> Option<Btc> {
+        self.daily_cap
+    }
+
+    /// Gets the confirmed balance that will always be kept in the account.
+    pub fn get_reserve(&self) -> Btc {
+        self.reserve
+    }
+
+    /// Gets the cost of each refill, including the service fee.
+    ///
+    /// It returns `None` if the amount is negative or the cost overflows.
+    pub fn get_refill_cost(&self) -> Option<Btc> {
+        calculate_service_fee(self.amount).and_then(|fee| self.amount.checked_add(fee))
+    }
+
+    /// Checks if the given confirmed balance can pay a refill of the given cost, keeping the
+    /// reserve and the fees.
+    fn can_afford(&self, balance: Btc, cost: Btc) -> bool {
+        balance.checked_sub(cost)
+            .and_then(|remaining| {
+                calculate_withdrawal_fee(remaining)
+                    .and_then(|fee| fee.checked_add(self.reserve))
+                    .and_then(|kept| kept.checked_add(ORDER_FEE))
+                    .map(|kept| remaining >= kept)
+            })
+            .unwrap_or(false)
+    }
+}
+
+/// Automatic refill of the orders of an account.
+///
+/// It does not run by itself: `check()` has to be called periodically, and it will refill the
+/// orders that need it.
+#[derive(Debug)]
+pub struct AutoRefill<'c> {
+    client: AuthenticatedClient<'c>,
+    policy: RefillPolicy,
+    refills: VecDeque<(Instant, Btc)>,
+}
+
+impl<'c> AutoRefill<'c> {
+    /// Creates a new automatic refill with the given policy.
+    ///
+    /// The client must have full access credentials, since the orders will be refilled.
+    pub fn new(client: AuthenticatedClient<'c>, policy: RefillPolicy) -> AutoRefill<'c> {
+        AutoRefill {
+            client: client,
+            policy: policy,
+            refills: VecDeque::new(),
+        }
+    }
+
+    /// Gets the refill policy.
+    pub fn get_policy(&self) -> RefillPolicy {
+        self.policy
+    }
+
+    /// Gets the BTCs spent in refills in the last 24 hours, fees included.
+    pub fn get_spent_today(&self) -> Btc {
+        let day = Duration::from_secs(DAY);
+        self.refills
+            .iter()
+            .filter(|&&(time, _)| time.elapsed() < day)
+            .fold(Btc::zero(), |spent, &(_, cost)| spent + cost)
+    }
+
+    /// Checks the orders of the given location and algorithm, and refills the alive ones that are
+    /// running out of BTCs.
+    ///
+    /// An order is only refilled if the account balance can pay for it, keeping the reserve of
+    /// the policy, and if the refill does not exceed the daily cap. It fails if the orders or the
+    /// balance cannot be retrieved. If a refill fails, the check stops and the refills performed
+    /// until then are returned along with the error.
+    pub fn check(&mut self, location: Location, algorithm: Algorithm) -> Result<RefillCheck> {
+        let day = Duration::from_secs(DAY);
+        while self.refills.front().map_or(false, |&(time, _)| time.elapsed() >= day) {
+            let _ = self.refills.pop_front();
+        }
+
+        let orders = self.client.get_my_orders(location, algorithm)?;
+        let mut balance = self.client.get_balance()?.confirmed;
+        let cost = self.policy
+            .get_refill_cost()
+            .ok_or_else(|| {
+                Error::AmountBelowMinimum(format!("refill amount {} BTC is negative or too big",
+                                                  self.policy.amount))
+            })?;
+
+        let mut refills = Vec::new();
+        for order in orders.into_iter().filter(|order| order.is_alive()) {
+            let available = match order.get_btc_available() {
+                Some(available) if available < self.policy.threshold => available,
+                _ => continue,
+            };
+            if self.policy.daily_cap.map_or(false, |cap| self.get_spent_today() + cost > cap) ||
+               !self.policy.can_afford(balance, cost) {
+                continue;
+            }
+
+            if let Err(e) = self.client
+                .refill_order(location, algorithm, order.get_id(), self.policy.amount) {
+                return Ok(RefillCheck {
+                    refills: refills,
+                    error: Some(e),
+                });
+            }
+            balance -= cost;
+            self.refills.push_back((Instant::now(), cost));
+            refills.push(Refill {
+                order_id: order.get_id(),
+                previous_btc_available: available,
+                cost: cost,
+            });
+        }
+        Ok(RefillCheck {
+            refills: refills,
+            error: None,
+        })
+    }
+}
+
+/// Result of an `AutoRefill::check()`.
+#[derive(Debug)]
+pub struct RefillCheck {
+    refills: Vec<Refill>,
+    error: Option<Error>,
+}
+
+impl RefillCheck {
+    /// Gets the refills performed.
+    pub fn get_refills(&self) -> &[Refill] {
+        &self.refills
+    }
+
+    /// Gets the error of the refill that stopped the check, if any.
+    pub fn get_error(&self) -> Option<&Error> {
+        self.error.as_ref()
+    }
+}
+
+/// Refill of an order performed by `AutoRefill`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Refill {
+    order_id: u64,
+    previous_btc_available: Btc,
+    cost: Btc,
+}
+
+impl Refill {
+    /// Gets the ID of the refilled order.
+    pub fn get_order_id(&self) -> u64 {
+        self.order_id
+    }
+
+    /// Gets the remaining BTCs of the order before the refill.
+    pub fn get_previous_btc_available(&self) -> Btc {
+        self.previous_btc_available
+    }
+
+    /// Gets the BTCs paid for the refill, including the service fee.
+    pub fn get_cost(&self) -> Btc {
+        self.cost
+    }
+}
@@ -0,0 +1,216 @@
+//! Decoding of the API responses.
+//!
+//! All the API methods answer with a JSON object with the payload of the response in its
+//! `result` object. If the request was not successful, the `result` object has an `error` field
+//! with the error message instead. `ApiResponse` checks this envelope, so that each method only
+//! has to convert the payload to its own type.
+
+use std::io::Read;
+use std::marker::PhantomData;
+use std::collections::BTreeMap;
+
+use serde_json::de;
+use serde_json::value::Value;
+
+use error::{Result, Error};
+use types::{GlobalStats, Order, BuyInfo, ProviderStats, ProviderStatsEx, Payment,
+            MultiAlgoInfo, Balance};
+
+/// Conversion from the JSON payload of an API response.
+pub trait FromValue: Sized {
+    /// Creates the value from the JSON payload of a response.
+    fn from_value(json: Value) -> Result<Self>;
+}
+
+/// Envelope of the response of an API method, with a payload of type `T`.
+#[derive(Debug)]
+pub struct ApiResponse<'a, T> {
+    method: &'a str,
+    field: Option<&'a str>,
+    ignored: &'a [&'a str],
+    payload: PhantomData<T>,
+}
+
+impl<'a, T: FromValue> ApiResponse<'a, T> {
+    /// Creates the envelope of the response of the given API method.
+    ///
+    /// By default, the payload is the whole `result` object of the response.
+    pub fn new(method: &'a str) -> ApiResponse<'a, T> {
+        ApiResponse {
+            method: method,
+            field: None,
+            ignored: &[],
+            payload: PhantomData,
+        }
+    }
+
+    /// Sets the field of the `result` object that contains the payload.
+    pub fn field(mut self, field: &'a str) -> ApiResponse<'a, T> {
+        self.field = Some(field);
+        self
+    }
+
+    /// Sets the fields of the `result` object that will be ignored if present.
+    ///
+    /// Any other field apart from the payload field will be reported as an error.
+    pub fn ignore(mut self, ignored: &'a [&'a str]) -> ApiResponse<'a, T> {
+        self.ignored = ignored;
+        self
+    }
+
+    /// Decodes the response read from the given reader, and returns its payload.
+    pub fn decode<R: Read>(&self, reader: R) -> Result<T> {
+        let mut result = match de::from_reader(reader)? {
+            Value::Object(mut response) => {
+                match response.remove("result") {
+                    Some(Value::Object(result)) => result,
+                    _ => {
+                        return Err(Error::Api(format!("invalid response to `{}` method, no \
+                                                       `result` field was found",
+                                                      self.method)))
+                    }
+                }
+            }
+            _ => return Err(Error::Api(format!("invalid response to `{}` method", self.method))),
+        };
+        if result.is_empty() {
+            return Err(Error::Api(format!("empty result response to `{}`", self.method)));
+        }
+        match result.remove("error") {
+            Some(Value::String(e)) => return Err(Error::from_result(e)),
+            Some(_) => {
+                return Err(Error::Api(format!("invalid `error` field found in `{}` response, \
+                                               expected string",
+                                              self.method)))
+            }
+            None => {}
+        }
+
+        let payload = match self.field {
+            Some(field) => {
+                let payload = result.remove(field)
+                    .ok_or_else(|| {
+                        Error::Api(format!("invalid response to `{}` method, no `{}` field was \
+                                            found",
+                                           self.method,
+                                           field))
+                    })?;
+                let ignored = self.ignored;
+                if let Some(key) = result.keys().find(|key| !ignored.contains(&key.as_str())) {
+                    return Err(Error::Api(format!("unknown field `{}` found in `{}` response",
+                                                  key,
+                                                  self.method)));
+                }
+                payload
+            }
+            None => Value::Object(result),
+        };
+        T::from_value(payload)
+    }
+}
+
+/// Payloads that are only checked for presence, such as the `success` message of the methods that
+/// modify orders.
+impl FromValue for () {
+    fn from_value(_: Value) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl FromValue for Value {
+    fn from_value(json: Value) -> Result<Value> {
+        Ok(json)
+    }
+}
+
+impl FromValue for BTreeMap<String, Value> {
+    fn from_value(json: Value) -> Result<BTreeMap<String, Value>> {
+        if let Value::Object(object) = json {
+            Ok(object)
+        } else {
+            Err(Error::Api("invalid object in response".to_owned()))
+        }
+    }
+}
+
+impl<T: FromValue> FromValue for Vec<T> {
+    fn from_value(json: Value) -> Result<Vec<T>> {
+        if let Value::Array(arr) = json {
+            let mut vec = Vec::with_capacity(arr.len());
+            for value in arr {
+                vec.push(T::from_value(value)?);
+            }
+            Ok(vec)
+        } else {
+            Err(Error::Api("invalid list in response, expected array".to_owned()))
+        }
+    }
+}
+
+impl FromValue for GlobalStats {
+    fn from_value(json: Value) -> Result<GlobalStats> {
+        if let Value::Array(stats) = json {
+            GlobalStats::from_json(stats)
+        } else {
+            Err(Error::Api("invalid global stats, expected array".to_owned()))
+        }
+    }
+}
+
+impl FromValue for Order {
+    fn from_value(json: Value) -> Result<Order> {
+        Order::from_json(json)
+    }
+}
+
+impl FromValue for BuyInfo {
+    fn from_value(json: Value) -> Result<BuyInfo> {
+        if let Value::Object(info) = json {
+            BuyInfo::from_json(info)
+        } else {
+            Err(Error::Api("invalid buy information object".to_owned()))
+        }
+    }
+}
+
+impl FromValue for ProviderStats {
+    fn from_value(json: Value) -> Result<ProviderStats> {
+        if let Value::Array(stats) = json {
+            ProviderStats::from_json(stats)
+        } else {
+            Err(Error::Api("invalid provider stats, expected array".to_owned()))
+        }
+    }
+}
+
+impl FromValue for ProviderStatsEx {
+    fn from_value(json: Value) -> Result<ProviderStatsEx> {
+        if let Value::Object(stats) = json {
+            ProviderStatsEx::from_json(stats)
+        } else {
+            Err(Error::Api("invalid provider stats object".to_owned()))
+        }
+    }
+}
+
+impl FromValue for Payment {
+    fn from_value(json: Value) -> Result<Payment> {
+        Payment::from_json(json)
+    }
+}
+
+impl FromValue for MultiAlgoInfo {
+    fn from_value(json: Value) -> Result<MultiAlgoInfo> {
+        if let Value::Array(info) = json {
+            MultiAlgoInfo::from_json(info)
+        } else {
+            Err(Error::Api("invalid multi-algorithm info, expected array".to_owned()))
+        }
+    }
+}
+
+impl FromValue for Balance {
+    fn from_value(json: Value) -> Result<Balance> {
+        Balance::from_json(json)
+    }
+}
@@ -0,0 +1,161 @@
+//! Retry policy for transient failures.
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::thread;
+use std::time::Duration;
+
+use error::{Result, Error};
+
+/// Policy to retry the API requests that fail with a transient error.
+///
+/// The requests are retried with an exponential backoff: the delay before the first retry is the
+/// initial backoff, and it doubles after each attempt, up to the maximum backoff. A random jitter
+/// is subtracted from each delay, so that many clients do not retry at the same time.
+///
+/// Only the methods that can be safely repeated (the ones that get information from the API) are
+/// retried by default. Methods that create or modify orders, such as `create_order()` or
+/// `refill_order()`, are only retried if `retry_non_idempotent()` is enabled, since a request
+/// that failed while reading the response might have been executed by the server.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    jitter: f64,
+    non_idempotent: bool,
+}
+
+impl RetryPolicy {
+    /// Creates a new retry policy with the given maximum number of attempts for each request.
+    ///
+    /// The initial backoff is 500 milliseconds, the maximum backoff is 30 seconds and the jitter
+    /// is 0.5.
+    pub fn new(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: max_attempts,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            jitter: 0.5,
+            non_idempotent: false,
+        }
+    }
+
+    /// Creates a retry policy that never retries the requests.
+    pub fn none() -> RetryPolicy {
+        RetryPolicy::new(1)
+    }
+
+    /// Sets the delay before the first retry.
+    pub fn initial_backoff(mut self, initial_backoff: Duration) -> RetryPolicy {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// Sets the maximum delay between two attempts.
+    pub fn max_backoff(mut self, max_backoff: Duration) -> RetryPolicy {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Sets the jitter, as the maximum fraction of each delay that will be randomly subtracted.
+    ///
+    /// It will be clamped between 0 (no jitter) and 1 (delays anywhere between 0 and the full
+    /// backoff).
+    pub fn jitter(mut self, jitter: f64) -> RetryPolicy {
+        self.jitter = jitter.max(0.0).min(1.0);
+        self
+    }
+
+    /// Sets whether the methods that create or modify orders should be retried too.
+    pub fn retry_non_idempotent(mut self, non_idempotent: bool) -> RetryPolicy {
+        self.non_idempotent = non_idempotent;
+        self
+    }
+
+    /// Gets the maximum number of attempts for each request.
+    pub fn get_max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Gets the delay before the first retry.
+    pub fn get_initial_backoff(&self) -> Duration {
+        self.initial_backoff
+    }
+
+    /// Gets the maximum delay between two attempts.
+    pub fn get_max_backoff(&self) -> Duration {
+        self.max_backoff
+    }
+
+    /// Gets the jitter of the delays.
+    pub fn get_jitter(&self) -> f64 {
+        self.jitter
+    }
+
+    /// Gets whether the methods that create or modify orders are retried.
+    pub fn retries_non_idempotent(&self) -> bool {
+        self.non_idempotent
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> RetryPolicy {
+        RetryPolicy::none()
+    }
+}
+
+/// Runs the given request, retrying it with the given policy while it fails with a transient
+/// error.
+pub fn with_retries<T, F>(policy: &RetryPolicy, idempotent: bool, mut request: F) -> Result<T>
+    where F: FnMut() -> Result<T>
+{
+    let attempts = if idempotent || policy.non_idempotent {
+        policy.max_attempts.max(1)
+    } else {
+        1
+    };
+    let mut attempt = 1;
+    loop {
+        match request() {
+            Err(ref e) if attempt < attempts && is_transient(e) => {
+                thread::sleep(backoff(policy, attempt));
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Checks if the given error is transient, so that the request can be retried.
+///
+/// Only transport errors, timeouts and rate limiting are transient. Errors reported by the API
+/// about the request itself, or responses that cannot be decoded, would fail again.
+fn is_transient(error: &Error) -> bool {
+    match *error {
+        Error::Hyper(_) |
+        Error::Timeout(_) |
+        Error::RateLimited(_) => true,
+        _ => false,
+    }
+}
+
+/// Gets the delay before the given retry, starting at 1 for the first retry.
+fn backoff(policy: &RetryPolicy, retry: u32) -> Duration {
+    let initial = duration_to_millis(policy.initial_backoff);
+    let max = duration_to_millis(policy.max_backoff);
+    let backoff = initial.saturating_mul(1u64 << retry.saturating_sub(1).min(32)).min(max);
+
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u32(retry);
+    let random = (hasher.finish() % 1_000_000) as f64 / 1_000_000.0;
+    let jitter = (backoff as f64 * policy.jitter * random) as u64;
+    Duration::from_millis(backoff - jitter.min(backoff))
+}
+
+/// Converts a duration to milliseconds.
+fn duration_to_millis(duration: Duration) -> u64 {
+    duration.as_secs()
+        .saturating_mul(1000)
+        .saturating_add(duration.subsec_nanos() as u64 / 1_000_000)
+}
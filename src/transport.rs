@@ -0,0 +1,139 @@
+//! HTTP transports used by the API client.
+//!
+//! The `Client` does not perform HTTP requests by itself: it builds the URL of each API call,
+//! with all its parameters in the query string, and hands it to a `Transport`, which returns the
+//! body of the response. The response is then parsed by the `Client`, so any HTTP stack, a
+//! recording proxy or a test double can be used by implementing this trait.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::io::{self, Read};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use hyper;
+use hyper::client::pool::{Pool, Config};
+use hyper::net::{HttpsConnector, HttpStream, NetworkConnector, OpensslClient};
+pub use hyper::Url;
+
+use error::Result;
+
+/// HTTP transport used to perform the API requests.
+///
+/// The transport must be `Send` and `Sync`, so that the client can be shared between threads.
+pub trait Transport: Debug + Send + Sync {
+    /// Performs a `GET` request to the given URL and returns the body of the response.
+    ///
+    /// The URL already contains the API method and all its parameters in the query string.
+    fn get(&self, url: Url) -> Result<Box<Read>>;
+
+    /// Performs a `GET` request with the given timeout, and returns the body of the response.
+    ///
+    /// The client calls this method with its timeout for every request. Transports should apply
+    /// the timeout to the connection and to each read and write, and fail with an I/O error of
+    /// kind `TimedOut` or `WouldBlock` when it expires. By default, the timeout is ignored and the
+    /// request is performed with `get()`.
+    fn get_with_timeout(&self, url: Url, timeout: Option<Duration>) -> Result<Box<Read>> {
+        let _ = timeout;
+        self.get(url)
+    }
+}
+
+/// Transport using a Hyper client.
+///
+/// The timeouts of the Hyper client are used, and the timeout of the API client is ignored.
+impl Transport for hyper::Client {
+    fn get(&self, url: Url) -> Result<Box<Read>> {
+        Ok(Box::new(hyper::Client::get(self, url).send()?))
+    }
+}
+
+/// Default HTTP transport of the client, using Hyper.
+///
+/// The timeout is set on the sockets of the requests: connecting to each address of the server,
+/// and each read and write, fail if they take longer than the timeout. Requests performed with a
+/// different timeout, for example with `Client::with_timeout()`, use another Hyper client, that
+/// is kept and reused for the following requests with the same timeout.
+#[derive(Debug)]
+pub struct HttpTransport {
+    client: hyper::Client,
+    timeout: Option<Duration>,
+    other_clients: Mutex<HashMap<Option<Duration>, Arc<hyper::Client>>>,
+}
+
+impl HttpTransport {
+    /// Creates a new HTTP transport with the given timeout.
+    pub fn new(timeout: Option<Duration>) -> HttpTransport {
+        HttpTransport {
+            client: hyper_client(timeout),
+            timeout: timeout,
+            other_clients: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Gets the timeout of the sockets of the transport.
+    pub fn get_timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+}
+
+impl Transport for HttpTransport {
+    fn get(&self, url: Url) -> Result<Box<Read>> {
+        Ok(Box::new(self.client.get(url).send()?))
+    }
+
+    fn get_with_timeout(&self, url: Url, timeout: Option<Duration>) -> Result<Box<Read>> {
+        if timeout == self.timeout {
+            return self.get(url);
+        }
+        // The lock is released before the request, so that requests can run concurrently.
+        let client = self.other_clients
+            .lock()
+            .unwrap()
+            .entry(timeout)
+            .or_insert_with(|| Arc::new(hyper_client(timeout)))
+            .clone();
+        Ok(Box::new(client.get(url).send()?))
+    }
+}
+
+/// Creates a Hyper client with a connection pool, that applies the given timeout to the
+/// connections and to each read and write.
+fn hyper_client(timeout: Option<Duration>) -> hyper::Client {
+    let connector = HttpsConnector::with_connector(OpensslClient::default(),
+                                                   TimeoutConnector { timeout: timeout });
+    let mut client = hyper::Client::with_connector(Pool::with_connector(Config::default(),
+                                                                        connector));
+    client.set_read_timeout(timeout);
+    client.set_write_timeout(timeout);
+    client
+}
+
+/// Connector that opens TCP connections with a connection timeout.
+#[derive(Debug, Clone, Copy)]
+struct TimeoutConnector {
+    timeout: Option<Duration>,
+}
+
+impl NetworkConnector for TimeoutConnector {
+    type Stream = HttpStream;
+
+    fn connect(&self, host: &str, port: u16, _scheme: &str) -> hyper::Result<HttpStream> {
+        let mut last_error = None;
+        for address in (host, port).to_socket_addrs()? {
+            let stream = match self.timeout {
+                Some(timeout) => TcpStream::connect_timeout(&address, timeout),
+                None => TcpStream::connect(address),
+            };
+            match stream {
+                Ok(stream) => return Ok(HttpStream(stream)),
+                Err(e) => last_error = Some(e),
+            }
+        }
+        Err(hyper::Error::Io(last_error.unwrap_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput,
+                           format!("no address found for host {}", host))
+        })))
+    }
+}
@@ -1,15 +1,19 @@
 //! Types used in the nicehash.com API.
 
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use std::collections::BTreeMap;
 
+#[cfg(feature = "serde")]
+use serde_json::builder::ObjectBuilder;
 use serde_json::value::Value;
 
-use super::{Algorithm, OrderType};
+use super::{Algorithm, OrderType, Location};
+use btc::Btc;
 use error::{Result, Error};
+use hashrate::{Hashrate, HashrateUnit};
 
 /// `GlobalStats` structure.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, PartialEq)]
 pub struct GlobalStats {
     scrypt: AlgoStat,
     sha256: AlgoStat,
@@ -47,9 +51,16 @@ impl GlobalStats {
                                           json.len())));
         }
         let mut stats = GlobalStats::default();
+        let mut found = Vec::with_capacity(json.len());
         for stat in json {
             if let Value::Object(stat) = stat {
                 let (algorithm, stat) = AlgoStat::from_json(stat)?;
+                if found.contains(&algorithm) {
+                    return Err(Error::Api(format!("duplicated algorithm {:?} found in global \
+                                                   stats",
+                                                  algorithm)));
+                }
+                found.push(algorithm);
                 match algorithm {
                     Algorithm::Scrypt => stats.scrypt = stat,
                     Algorithm::SHA256 => stats.sha256 = stat,
@@ -114,10 +125,25 @@ impl GlobalStats {
             Algorithm::Equihash => &self.equihash,
         }
     }
+
+    /// Converts the `GlobalStats` to the JSON value used by the API.
+    ///
+    /// It is the array in the `stats` field of the response, with the stats of each algorithm.
+    /// The API always returns the 25 algorithms, so all of them are included.
+    #[cfg(feature = "serde")]
+    fn to_json(&self) -> Value {
+        Value::Array(Algorithm::all()
+            .iter()
+            .map(|alg| json_with_algorithm(self.get_stats_for(*alg).to_json(), *alg))
+            .collect())
+    }
 }
 
 /// Statistics about an algorithm.
-#[derive(Debug, Default)]
+///
+/// The price, the speed and the profitabilities are floats, since they are averages and
+/// estimates that the API does not round to satoshis.
+#[derive(Debug, Default, PartialEq)]
 pub struct AlgoStat {
     price: f64,
     speed: f64,
@@ -138,7 +164,11 @@ impl AlgoStat {
             .ok_or_else(|| {
                 Error::Api("invalid algorithm in stats (must be an unsigned integer)".to_owned())
             })?)?;
+        Ok((algorithm, AlgoStat::from_json_fields(&json)?))
+    }
 
+    /// Creates an `AlgoStat` from the fields of a JSON value, ignoring the algorithm.
+    fn from_json_fields(json: &BTreeMap<String, Value>) -> Result<AlgoStat> {
         let profitability_above_btc = match json.get("profitability_above_btc") {
             Some(v) => {
                 Some(v.as_str()
@@ -214,8 +244,7 @@ impl AlgoStat {
             None => None,
         };
 
-        Ok((algorithm,
-            AlgoStat {
+        Ok(AlgoStat {
             price: json.get("price")
                 .ok_or_else(|| Error::Api("`price` not found in stats".to_owned()))?
                 .as_str()
@@ -236,10 +265,32 @@ impl AlgoStat {
             profitability_eth: profitability_eth,
             profitability_above_ltc: profitability_above_ltc,
             profitability_ltc: profitability_ltc,
-        }))
+        })
+    }
+
+    /// Converts the `AlgoStat` to the JSON value used by the API, without the algorithm.
+    #[cfg(feature = "serde")]
+    fn to_json(&self) -> Value {
+        let mut stat = ObjectBuilder::new()
+            .insert("price", float_to_json(self.price))
+            .insert("speed", float_to_json(self.speed));
+        let profitabilities = [("profitability_above_btc", self.profitability_above_btc),
+                               ("profitability_btc", self.profitability_btc),
+                               ("profitability_above_eth", self.profitability_above_eth),
+                               ("profitability_eth", self.profitability_eth),
+                               ("profitability_above_ltc", self.profitability_above_ltc),
+                               ("profitability_ltc", self.profitability_ltc)];
+        for &(key, profitability) in &profitabilities {
+            if let Some(profitability) = profitability {
+                stat = stat.insert(key, float_to_json(profitability));
+            }
+        }
+        stat.build()
     }
 
-    /// Gets the price
+    /// Gets the price paid for the algorithm, in BTCs per unit of speed per day.
+    ///
+    /// It is an average, so it is not rounded to satoshis.
     pub fn get_price(&self) -> f64 {
         self.price
     }
@@ -249,6 +300,11 @@ impl AlgoStat {
         self.speed
     }
 
+    /// Gets the hashing speed, with the unit given in the buy information of the algorithm.
+    pub fn get_hashrate(&self, buy_info: &AlgoBuyInfo) -> Result<Hashrate> {
+        Hashrate::from_native(self.speed, buy_info)
+    }
+
     /// Gets the profitability above BTC.
     pub fn get_profitability_above_btc(&self) -> Option<f64> {
         self.profitability_above_btc
@@ -280,17 +336,916 @@ impl AlgoStat {
     }
 }
 
+/// Statistics of a hashing power provider.
+#[derive(Debug, Default, PartialEq)]
+pub struct ProviderStats {
+    stats: Vec<ProviderAlgoStat>,
+}
+
+impl ProviderStats {
+    /// Creates a `ProviderStats` object from a JSON value.
+    pub fn from_json(json: Vec<Value>) -> Result<ProviderStats> {
+        let mut stats = Vec::with_capacity(json.len());
+        for stat in json {
+            stats.push(ProviderAlgoStat::from_json(stat)?);
+        }
+        Ok(ProviderStats { stats: stats })
+    }
+
+    /// Gets stats for the given algorithm, if the provider has mined with it.
+    pub fn get_stats_for(&self, alg: Algorithm) -> Option<&ProviderAlgoStat> {
+        self.stats.iter().find(|stat| stat.algorithm == alg)
+    }
+
+    /// Gets stats for all the algorithms the provider has mined with.
+    pub fn get_all_stats(&self) -> &[ProviderAlgoStat] {
+        &self.stats
+    }
+
+    /// Converts the `ProviderStats` to the JSON value used by the API.
+    ///
+    /// It is the array in the `stats` field of the response.
+    #[cfg(feature = "serde")]
+    fn to_json(&self) -> Value {
+        Value::Array(self.stats.iter().map(ProviderAlgoStat::to_json).collect())
+    }
+}
+
+/// Statistics of a hashing power provider for an algorithm.
+#[derive(Debug, PartialEq)]
+pub struct ProviderAlgoStat {
+    algorithm: Algorithm,
+    balance: Btc,
+    accepted_speed: f64,
+    rejected_speed: f64,
+}
+
+impl ProviderAlgoStat {
+    /// Creates a `ProviderAlgoStat` from a JSON value.
+    fn from_json(json: Value) -> Result<ProviderAlgoStat> {
+        if let Value::Object(stat) = json {
+            let algorithm = Algorithm::from_u64(stat.get("algo")
+                .ok_or_else(|| Error::Api("`algo` not found in provider stats".to_owned()))?
+                .as_u64()
+                .ok_or_else(|| {
+                    Error::Api("invalid algorithm in provider stats (must be an unsigned integer)"
+                        .to_owned())
+                })?)?;
+
+            Ok(ProviderAlgoStat {
+                algorithm: algorithm,
+                balance: stat.get("balance")
+                    .ok_or_else(|| Error::Api("`balance` not found in provider stats".to_owned()))?
+                    .as_str()
+                    .ok_or_else(|| {
+                        Error::Api("invalid `balance` in provider stats (must be a BTC amount \
+                                    in a string)"
+                            .to_owned())
+                    })?
+                    .parse()?,
+                accepted_speed: stat.get("accepted_speed")
+                    .ok_or_else(|| {
+                        Error::Api("`accepted_speed` not found in provider stats".to_owned())
+                    })?
+                    .as_str()
+                    .ok_or_else(|| {
+                        Error::Api("invalid `accepted_speed` in provider stats (must be a float \
+                                    in a string)"
+                            .to_owned())
+                    })?
+                    .parse()?,
+                rejected_speed: stat.get("rejected_speed")
+                    .ok_or_else(|| {
+                        Error::Api("`rejected_speed` not found in provider stats".to_owned())
+                    })?
+                    .as_str()
+                    .ok_or_else(|| {
+                        Error::Api("invalid `rejected_speed` in provider stats (must be a float \
+                                    in a string)"
+                            .to_owned())
+                    })?
+                    .parse()?,
+            })
+        } else {
+            Err(Error::Api("invalid provider stats object".to_owned()))
+        }
+    }
+
+    /// Gets the algorithm of the stats.
+    pub fn get_algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    /// Gets the unpaid balance of the provider for the algorithm, in BTCs.
+    pub fn get_balance(&self) -> Btc {
+        self.balance
+    }
+
+    /// Gets the accepted hashing speed.
+    pub fn get_accepted_speed(&self) -> f64 {
+        self.accepted_speed
+    }
+
+    /// Gets the rejected hashing speed.
+    pub fn get_rejected_speed(&self) -> f64 {
+        self.rejected_speed
+    }
+
+    /// Converts the `ProviderAlgoStat` to the JSON value used by the API.
+    #[cfg(feature = "serde")]
+    fn to_json(&self) -> Value {
+        ObjectBuilder::new()
+            .insert("algo", self.algorithm as u64)
+            .insert("balance", btc_to_json(self.balance))
+            .insert("accepted_speed", float_to_json(self.accepted_speed))
+            .insert("rejected_speed", float_to_json(self.rejected_speed))
+            .build()
+    }
+}
+
+/// Detailed statistics of a hashing power provider.
+#[derive(Debug, Default, PartialEq)]
+pub struct ProviderStatsEx {
+    addr: Option<String>,
+    current: Vec<ProviderCurrentStat>,
+    past: Vec<ProviderHistory>,
+    payments: Option<Vec<Payment>>,
+}
+
+impl ProviderStatsEx {
+    /// Creates a `ProviderStatsEx` object from a JSON value.
+    pub fn from_json(json: BTreeMap<String, Value>) -> Result<ProviderStatsEx> {
+        let mut stats = ProviderStatsEx::default();
+        for (key, value) in json {
+            match key.as_str() {
+                "current" => {
+                    if let Value::Array(arr) = value {
+                        for stat in arr {
+                            stats.current.push(ProviderCurrentStat::from_json(stat)?);
+                        }
+                    } else {
+                        return Err(Error::Api("invalid `current` in provider stats, expected \
+                                               array"
+                            .to_owned()));
+                    }
+                }
+                "past" => {
+                    if let Value::Array(arr) = value {
+                        for history in arr {
+                            stats.past.push(ProviderHistory::from_json(history)?);
+                        }
+                    } else {
+                        return Err(Error::Api("invalid `past` in provider stats, expected array"
+                            .to_owned()));
+                    }
+                }
+                "addr" => {
+                    stats.addr = Some(value.as_str()
+                        .ok_or_else(|| {
+                            Error::Api("invalid `addr` in provider stats (must be a string)"
+                                .to_owned())
+                        })?
+                        .to_owned());
+                }
+                "payments" => {
+                    if let Value::Array(arr) = value {
+                        let mut payments = Vec::with_capacity(arr.len());
+                        for payment in arr {
+                            payments.push(Payment::from_json(payment)?);
+                        }
+                        stats.payments = Some(payments);
+                    } else {
+                        return Err(Error::Api("invalid `payments` in provider stats, expected \
+                                               array"
+                            .to_owned()));
+                    }
+                }
+                k => {
+                    return Err(Error::Api(format!("unknown key `{}` found in provider stats \
+                                                   JSON object",
+                                                  k)));
+                }
+            }
+        }
+        Ok(stats)
+    }
+
+    /// Gets the address of the provider, if the API returned it.
+    pub fn get_addr(&self) -> Option<&str> {
+        self.addr.as_ref().map(String::as_str)
+    }
+
+    /// Gets current stats for the given algorithm, if the provider is mining with it.
+    pub fn get_current_for(&self, alg: Algorithm) -> Option<&ProviderCurrentStat> {
+        self.current.iter().find(|stat| stat.algorithm == alg)
+    }
+
+    /// Gets current stats for all the algorithms the provider is mining with.
+    pub fn get_current(&self) -> &[ProviderCurrentStat] {
+        &self.current
+    }
+
+    /// Gets historical stats for the given algorithm, if the provider has mined with it.
+    pub fn get_history_for(&self, alg: Algorithm) -> Option<&ProviderHistory> {
+        self.past.iter().find(|history| history.algorithm == alg)
+    }
+
+    /// Gets historical stats for all the algorithms the provider has mined with.
+    pub fn get_history(&self) -> &[ProviderHistory] {
+        &self.past
+    }
+
+    /// Gets the last payments to the provider, if the API returned them.
+    pub fn get_payments(&self) -> &[Payment] {
+        self.payments.as_ref().map_or(&[], |payments| payments.as_slice())
+    }
+
+    /// Converts the `ProviderStatsEx` to the JSON value used by the API.
+    #[cfg(feature = "serde")]
+    fn to_json(&self) -> Value {
+        let mut stats = ObjectBuilder::new()
+            .insert("current",
+                    Value::Array(self.current.iter().map(ProviderCurrentStat::to_json).collect()))
+            .insert("past",
+                    Value::Array(self.past.iter().map(ProviderHistory::to_json).collect()));
+        if let Some(ref addr) = self.addr {
+            stats = stats.insert("addr", addr.as_str());
+        }
+        if let Some(ref payments) = self.payments {
+            stats = stats.insert("payments",
+                                 Value::Array(payments.iter().map(Payment::to_json).collect()));
+        }
+        stats.build()
+    }
+}
+
+/// Current statistics of a hashing power provider for an algorithm.
+#[derive(Debug, PartialEq)]
+pub struct ProviderCurrentStat {
+    algorithm: Algorithm,
+    name: Option<String>,
+    suffix: String,
+    profitability: f64,
+    speeds: BTreeMap<String, f64>,
+    balance: Btc,
+}
+
+impl ProviderCurrentStat {
+    /// Creates a `ProviderCurrentStat` from a JSON value.
+    fn from_json(json: Value) -> Result<ProviderCurrentStat> {
+        if let Value::Object(stat) = json {
+            let algorithm = Algorithm::from_u64(stat.get("algo")
+                .ok_or_else(|| Error::Api("`algo` not found in provider stats".to_owned()))?
+                .as_u64()
+                .ok_or_else(|| {
+                    Error::Api("invalid algorithm in provider stats (must be an unsigned integer)"
+                        .to_owned())
+                })?)?;
+            let name = match stat.get("name") {
+                Some(name) => {
+                    Some(name.as_str()
+                        .ok_or_else(|| {
+                            Error::Api("invalid `name` in provider stats (must be a string)"
+                                .to_owned())
+                        })?
+                        .to_owned())
+                }
+                None => None,
+            };
+            let suffix = stat.get("suffix")
+                .ok_or_else(|| Error::Api("`suffix` not found in provider stats".to_owned()))?
+                .as_str()
+                .ok_or_else(|| {
+                    Error::Api("invalid `suffix` in provider stats (must be a string)".to_owned())
+                })?
+                .to_owned();
+            let profitability = stat.get("profitability")
+                .ok_or_else(|| {
+                    Error::Api("`profitability` not found in provider stats".to_owned())
+                })?
+                .as_str()
+                .ok_or_else(|| {
+                    Error::Api("invalid `profitability` in provider stats (must be a float in a \
+                                string)"
+                        .to_owned())
+                })?
+                .parse()?;
+
+            let data = stat.get("data")
+                .ok_or_else(|| Error::Api("`data` not found in provider stats".to_owned()))?
+                .as_array()
+                .ok_or_else(|| {
+                    Error::Api("invalid `data` in provider stats (must be an array)".to_owned())
+                })?;
+            if data.len() != 2 {
+                return Err(Error::Api(format!("provider stats data must have 2 elements but it \
+                                               had {}",
+                                              data.len())));
+            }
+            let speeds = parse_provider_speeds(&data[0])?;
+            let balance = data[1]
+                .as_str()
+                .ok_or_else(|| {
+                    Error::Api("invalid balance in provider stats (must be a BTC amount in a \
+                                string)"
+                        .to_owned())
+                })?
+                .parse()?;
+
+            Ok(ProviderCurrentStat {
+                algorithm: algorithm,
+                name: name,
+                suffix: suffix,
+                profitability: profitability,
+                speeds: speeds,
+                balance: balance,
+            })
+        } else {
+            Err(Error::Api("invalid provider stats object".to_owned()))
+        }
+    }
+
+    /// Gets the algorithm of the stats.
+    pub fn get_algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    /// Gets the name of the algorithm, as used by NiceHash, if the API returned it.
+    pub fn get_name(&self) -> Option<&str> {
+        self.name.as_ref().map(String::as_str)
+    }
+
+    /// Gets the text for the unit of the speed of the algorithm.
+    pub fn get_speed_text(&self) -> &str {
+        &self.suffix
+    }
+
+    /// Gets the current profitability of the algorithm, in BTCs per unit of speed per day.
+    pub fn get_profitability(&self) -> f64 {
+        self.profitability
+    }
+
+    /// Gets the accepted hashing speed.
+    pub fn get_accepted_speed(&self) -> f64 {
+        accepted_speed(&self.speeds)
+    }
+
+    /// Gets the rejected hashing speed, for all the rejection reasons.
+    pub fn get_rejected_speed(&self) -> f64 {
+        rejected_speed(&self.speeds)
+    }
+
+    /// Gets the unpaid balance of the provider for the algorithm, in BTCs.
+    pub fn get_balance(&self) -> Btc {
+        self.balance
+    }
+
+    /// Converts the `ProviderCurrentStat` to the JSON value used by the API.
+    #[cfg(feature = "serde")]
+    fn to_json(&self) -> Value {
+        let mut stat = ObjectBuilder::new()
+            .insert("algo", self.algorithm as u64)
+            .insert("suffix", self.suffix.as_str())
+            .insert("profitability", float_to_json(self.profitability))
+            .insert("data",
+                    Value::Array(vec![provider_speeds_to_json(&self.speeds),
+                                      btc_to_json(self.balance)]));
+        if let Some(ref name) = self.name {
+            stat = stat.insert("name", name.as_str());
+        }
+        stat.build()
+    }
+}
+
+/// Historical statistics of a hashing power provider for an algorithm.
+#[derive(Debug, PartialEq)]
+pub struct ProviderHistory {
+    algorithm: Algorithm,
+    samples: Vec<ProviderSample>,
+}
+
+impl ProviderHistory {
+    /// Creates a `ProviderHistory` from a JSON value.
+    fn from_json(json: Value) -> Result<ProviderHistory> {
+        if let Value::Object(history) = json {
+            let algorithm = Algorithm::from_u64(history.get("algo")
+                .ok_or_else(|| Error::Api("`algo` not found in provider history".to_owned()))?
+                .as_u64()
+                .ok_or_else(|| {
+                    Error::Api("invalid algorithm in provider history (must be an unsigned \
+                                integer)"
+                        .to_owned())
+                })?)?;
+            let data = history.get("data")
+                .ok_or_else(|| Error::Api("`data` not found in provider history".to_owned()))?
+                .as_array()
+                .ok_or_else(|| {
+                    Error::Api("invalid `data` in provider history (must be an array)".to_owned())
+                })?;
+
+            let mut samples = Vec::with_capacity(data.len());
+            for sample in data {
+                samples.push(ProviderSample::from_json(sample)?);
+            }
+            Ok(ProviderHistory {
+                algorithm: algorithm,
+                samples: samples,
+            })
+        } else {
+            Err(Error::Api("invalid provider history object".to_owned()))
+        }
+    }
+
+    /// Gets the algorithm of the history.
+    pub fn get_algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    /// Gets the historical samples, from oldest to newest.
+    pub fn get_samples(&self) -> &[ProviderSample] {
+        &self.samples
+    }
+
+    /// Converts the `ProviderHistory` to the JSON value used by the API.
+    #[cfg(feature = "serde")]
+    fn to_json(&self) -> Value {
+        ObjectBuilder::new()
+            .insert("algo", self.algorithm as u64)
+            .insert("data",
+                    Value::Array(self.samples.iter().map(ProviderSample::to_json).collect()))
+            .build()
+    }
+}
+
+/// Historical sample of the statistics of a hashing power provider.
+#[derive(Debug, PartialEq)]
+pub struct ProviderSample {
+    time: SystemTime,
+    speeds: BTreeMap<String, f64>,
+    balance: Btc,
+}
+
+impl ProviderSample {
+    /// Creates a `ProviderSample` from a JSON value.
+    ///
+    /// Samples are arrays with the time of the sample (in 5 minute units since the UNIX epoch),
+    /// the speeds object and the balance.
+    fn from_json(json: &Value) -> Result<ProviderSample> {
+        let sample = json.as_array()
+            .ok_or_else(|| Error::Api("invalid provider history sample".to_owned()))?;
+        if sample.len() != 3 {
+            return Err(Error::Api(format!("provider history sample must have 3 elements but \
+                                           it had {}",
+                                          sample.len())));
+        }
+        let time = sample[0]
+            .as_u64()
+            .ok_or_else(|| {
+                Error::Api("invalid time in provider history sample (must be an unsigned integer)"
+                    .to_owned())
+            })?;
+        let speeds = parse_provider_speeds(&sample[1])?;
+        let balance = sample[2]
+            .as_str()
+            .ok_or_else(|| {
+                Error::Api("invalid balance in provider history sample (must be a BTC amount \
+                            in a string)"
+                    .to_owned())
+            })?
+            .parse()?;
+        let time = time.checked_mul(300)
+            .and_then(|secs| UNIX_EPOCH.checked_add(Duration::from_secs(secs)))
+            .ok_or_else(|| Error::Api(format!("time {} in provider history sample is out of range",
+                                              time)))?;
+
+        Ok(ProviderSample {
+            time: time,
+            speeds: speeds,
+            balance: balance,
+        })
+    }
+
+    /// Gets the time of the sample.
+    pub fn get_time(&self) -> SystemTime {
+        self.time
+    }
+
+    /// Gets the accepted hashing speed.
+    pub fn get_accepted_speed(&self) -> f64 {
+        accepted_speed(&self.speeds)
+    }
+
+    /// Gets the rejected hashing speed, for all the rejection reasons.
+    pub fn get_rejected_speed(&self) -> f64 {
+        rejected_speed(&self.speeds)
+    }
+
+    /// Gets the unpaid balance of the provider at the time of the sample, in BTCs.
+    pub fn get_balance(&self) -> Btc {
+        self.balance
+    }
+
+    /// Converts the `ProviderSample` to the JSON value used by the API.
+    #[cfg(feature = "serde")]
+    fn to_json(&self) -> Value {
+        let time = self.time.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+        Value::Array(vec![Value::U64(time / 300),
+                          provider_speeds_to_json(&self.speeds),
+                          btc_to_json(self.balance)])
+    }
+}
+
+/// Payment to a hashing power provider.
+#[derive(Debug, PartialEq)]
+pub struct Payment {
+    amount: Btc,
+    fee: Btc,
+    transaction_id: String,
+    time: SystemTime,
+}
+
+impl Payment {
+    /// Creates a new `Payment` from a JSON value.
+    pub fn from_json(json: Value) -> Result<Payment> {
+        if let Value::Object(v) = json {
+            let amount = v.get("amount")
+                .ok_or_else(|| Error::Api("no `amount` parameter found in the payment".to_owned()))?
+                .as_str()
+                .ok_or_else(|| Error::Api("invalid payment amount".to_owned()))?
+                .parse()?;
+
+            let fee = v.get("fee")
+                .ok_or_else(|| Error::Api("no `fee` parameter found in the payment".to_owned()))?
+                .as_str()
+                .ok_or_else(|| Error::Api("invalid payment fee".to_owned()))?
+                .parse()?;
+
+            let transaction_id = v.get("TXID")
+                .ok_or_else(|| Error::Api("no `TXID` parameter found in the payment".to_owned()))?
+                .as_str()
+                .ok_or_else(|| Error::Api("invalid payment transaction ID".to_owned()))?
+                .to_owned();
+
+            let time = v.get("time")
+                .ok_or_else(|| Error::Api("no `time` parameter found in the payment".to_owned()))?
+                .as_u64()
+                .ok_or_else(|| Error::Api("invalid payment time".to_owned()))?;
+
+            Ok(Payment {
+                amount: amount,
+                fee: fee,
+                transaction_id: transaction_id,
+                time: UNIX_EPOCH + Duration::from_secs(time),
+            })
+        } else {
+            Err(Error::Api("invalid payment object".to_owned()))
+        }
+    }
+
+    /// Gets the amount paid, in BTCs.
+    pub fn get_amount(&self) -> Btc {
+        self.amount
+    }
+
+    /// Gets the fee charged for the payment, in BTCs.
+    pub fn get_fee(&self) -> Btc {
+        self.fee
+    }
+
+    /// Gets the ID of the Bitcoin transaction of the payment.
+    pub fn get_transaction_id(&self) -> &str {
+        &self.transaction_id
+    }
+
+    /// Gets the time of the payment.
+    pub fn get_time(&self) -> SystemTime {
+        self.time
+    }
+
+    /// Converts the `Payment` to the JSON value used by the API.
+    #[cfg(feature = "serde")]
+    fn to_json(&self) -> Value {
+        ObjectBuilder::new()
+            .insert("amount", btc_to_json(self.amount))
+            .insert("fee", btc_to_json(self.fee))
+            .insert("TXID", self.transaction_id.as_str())
+            .insert("time",
+                    self.time.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0))
+            .build()
+    }
+}
+
+/// Worker of a hashing power provider.
+#[derive(Debug, PartialEq)]
+pub struct ProviderWorker {
+    name: String,
+    algorithm: Algorithm,
+    speeds: BTreeMap<String, f64>,
+    connected_time: Duration,
+    xnsub: bool,
+    difficulty: f64,
+    location: Location,
+}
+
+impl ProviderWorker {
+    /// Creates a new `ProviderWorker` from a JSON value.
+    ///
+    /// Workers are arrays with the name of the worker, the speeds object, the connection time in
+    /// minutes, the XNSUB flag, the difficulty and the location. If the algorithm was not
+    /// specified in the request, the algorithm of the worker comes as the last element.
+    pub fn from_json(json: Value, algorithm: Option<Algorithm>) -> Result<ProviderWorker> {
+        let worker = match json {
+            Value::Array(worker) => worker,
+            _ => return Err(Error::Api("invalid provider worker".to_owned())),
+        };
+        let expected_len = if algorithm.is_some() { 6 } else { 7 };
+        if worker.len() < expected_len {
+            return Err(Error::Api(format!("provider worker must have {} elements but it had {}",
+                                          expected_len,
+                                          worker.len())));
+        }
+
+        let name = worker[0]
+            .as_str()
+            .ok_or_else(|| Error::Api("invalid provider worker name".to_owned()))?
+            .to_owned();
+        let speeds = parse_provider_speeds(&worker[1])?;
+        let connected_time = worker[2]
+            .as_u64()
+            .and_then(|minutes| minutes.checked_mul(60))
+            .ok_or_else(|| Error::Api("invalid provider worker connection time".to_owned()))?;
+        let xnsub = match worker[3].as_u64() {
+            Some(0) => false,
+            Some(1) => true,
+            _ => return Err(Error::Api("invalid provider worker XNSUB flag".to_owned())),
+        };
+        let difficulty = worker[4]
+            .as_str()
+            .ok_or_else(|| Error::Api("invalid provider worker difficulty".to_owned()))?
+            .parse()?;
+        let location = Location::from_u64(worker[5]
+            .as_u64()
+            .ok_or_else(|| Error::Api("invalid provider worker location".to_owned()))?)?;
+        let algorithm = match algorithm {
+            Some(algorithm) => algorithm,
+            None => {
+                Algorithm::from_u64(worker[6]
+                    .as_u64()
+                    .ok_or_else(|| Error::Api("invalid provider worker algorithm".to_owned()))?)?
+            }
+        };
+
+        Ok(ProviderWorker {
+            name: name,
+            algorithm: algorithm,
+            speeds: speeds,
+            connected_time: Duration::from_secs(connected_time),
+            xnsub: xnsub,
+            difficulty: difficulty,
+            location: location,
+        })
+    }
+
+    /// Gets the name of the worker (rig).
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Gets the algorithm the worker is mining with.
+    pub fn get_algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    /// Gets the accepted hashing speed of the worker.
+    pub fn get_accepted_speed(&self) -> f64 {
+        accepted_speed(&self.speeds)
+    }
+
+    /// Gets the rejected hashing speed of the worker, for all the rejection reasons.
+    pub fn get_rejected_speed(&self) -> f64 {
+        rejected_speed(&self.speeds)
+    }
+
+    /// Gets the time the worker has been connected.
+    pub fn get_connected_time(&self) -> Duration {
+        self.connected_time
+    }
+
+    /// Gets if the worker uses extranonce subscription (XNSUB) or not.
+    pub fn is_xnsub(&self) -> bool {
+        self.xnsub
+    }
+
+    /// Gets the current difficulty of the worker.
+    pub fn get_difficulty(&self) -> f64 {
+        self.difficulty
+    }
+
+    /// Gets the location of the server the worker is connected to.
+    pub fn get_location(&self) -> Location {
+        self.location
+    }
+
+    /// Converts the `ProviderWorker` to the JSON value used by the API.
+    ///
+    /// The algorithm of the worker is always added as the last element, so the value can be
+    /// parsed back with `from_json()` without specifying the algorithm.
+    #[cfg(feature = "serde")]
+    fn to_json(&self) -> Value {
+        Value::Array(vec![Value::String(self.name.clone()),
+                          provider_speeds_to_json(&self.speeds),
+                          Value::U64(self.connected_time.as_secs() / 60),
+                          Value::U64(self.xnsub as u64),
+                          float_to_json(self.difficulty),
+                          Value::U64(self.location as u64),
+                          Value::U64(self.algorithm as u64)])
+    }
+}
+
+/// Parses the speeds object of the provider stats, keeping the speed of each key.
+///
+/// The accepted speed is in the `a` key, and the rejected speeds are in keys starting with `r`
+/// (`rt`, `rs`, `rd` and `ro`, depending on the rejection reason). Missing keys mean no speed.
+fn parse_provider_speeds(json: &Value) -> Result<BTreeMap<String, f64>> {
+    let speeds = json.as_object()
+        .ok_or_else(|| Error::Api("invalid speeds object in provider stats".to_owned()))?;
+    let mut parsed = BTreeMap::new();
+    for (key, value) in speeds {
+        let speed = value.as_str()
+            .ok_or_else(|| {
+                Error::Api(format!("invalid `{}` speed in provider stats (must be a float in a \
+                                    string)",
+                                   key))
+            })?
+            .parse()?;
+        let _ = parsed.insert(key.clone(), speed);
+    }
+    Ok(parsed)
+}
+
+/// Gets the accepted speed from the parsed speeds object of the provider stats.
+fn accepted_speed(speeds: &BTreeMap<String, f64>) -> f64 {
+    speeds.get("a").cloned().unwrap_or(0.0)
+}
+
+/// Gets the rejected speed, for all the rejection reasons, from the parsed speeds object of the
+/// provider stats.
+fn rejected_speed(speeds: &BTreeMap<String, f64>) -> f64 {
+    speeds.iter()
+        .filter(|&(key, _)| key.starts_with('r'))
+        .fold(0.0, |rejected, (_, speed)| rejected + speed)
+}
+
+/// Converts the parsed speeds of a provider back to the speeds object of the API.
+#[cfg(feature = "serde")]
+fn provider_speeds_to_json(speeds: &BTreeMap<String, f64>) -> Value {
+    Value::Object(speeds.iter()
+        .map(|(key, speed)| (key.clone(), float_to_json(*speed)))
+        .collect())
+}
+
+/// Multi-algorithm mining information.
+#[derive(Debug, Default, PartialEq)]
+pub struct MultiAlgoInfo {
+    algorithms: Vec<AlgoMultiInfo>,
+}
+
+impl MultiAlgoInfo {
+    /// Creates a `MultiAlgoInfo` object from a JSON value.
+    pub fn from_json(json: Vec<Value>) -> Result<MultiAlgoInfo> {
+        let mut algorithms = Vec::with_capacity(json.len());
+        for info in json {
+            algorithms.push(AlgoMultiInfo::from_json(info)?);
+        }
+        Ok(MultiAlgoInfo { algorithms: algorithms })
+    }
+
+    /// Gets multi-algorithm mining information for the given algorithm, if available.
+    pub fn get_info_for(&self, alg: Algorithm) -> Option<&AlgoMultiInfo> {
+        self.algorithms.iter().find(|info| info.algorithm == alg)
+    }
+
+    /// Gets multi-algorithm mining information for all the available algorithms.
+    pub fn get_all_info(&self) -> &[AlgoMultiInfo] {
+        &self.algorithms
+    }
+
+    /// Converts the `MultiAlgoInfo` to the JSON value used by the API.
+    #[cfg(feature = "serde")]
+    fn to_json(&self) -> Value {
+        Value::Array(self.algorithms.iter().map(AlgoMultiInfo::to_json).collect())
+    }
+}
+
+/// Multi-algorithm mining information for an algorithm.
+#[derive(Debug, PartialEq)]
+pub struct AlgoMultiInfo {
+    algorithm: Algorithm,
+    name: String,
+    paying: f64,
+    port: u16,
+}
+
+impl AlgoMultiInfo {
+    /// Creates an `AlgoMultiInfo` from a JSON value.
+    fn from_json(json: Value) -> Result<AlgoMultiInfo> {
+        if let Value::Object(info) = json {
+            let algorithm = Algorithm::from_u64(info.get("algo")
+                .ok_or_else(|| Error::Api("`algo` not found in multi-algorithm info".to_owned()))?
+                .as_u64()
+                .ok_or_else(|| {
+                    Error::Api("invalid algorithm in multi-algorithm info (must be an unsigned \
+                                integer)"
+                        .to_owned())
+                })?)?;
+            let port = info.get("port")
+                .ok_or_else(|| Error::Api("`port` not found in multi-algorithm info".to_owned()))?
+                .as_u64()
+                .ok_or_else(|| {
+                    Error::Api("invalid `port` in multi-algorithm info (must be an unsigned \
+                                integer)"
+                        .to_owned())
+                })?;
+            if port > u16::max_value() as u64 {
+                return Err(Error::Api(format!("invalid port {} in multi-algorithm info", port)));
+            }
+
+            Ok(AlgoMultiInfo {
+                algorithm: algorithm,
+                name: info.get("name")
+                    .ok_or_else(|| {
+                        Error::Api("`name` not found in multi-algorithm info".to_owned())
+                    })?
+                    .as_str()
+                    .ok_or_else(|| {
+                        Error::Api("invalid `name` in multi-algorithm info (must be a string)"
+                            .to_owned())
+                    })?
+                    .to_owned(),
+                paying: info.get("paying")
+                    .ok_or_else(|| {
+                        Error::Api("`paying` not found in multi-algorithm info".to_owned())
+                    })?
+                    .as_str()
+                    .ok_or_else(|| {
+                        Error::Api("invalid `paying` in multi-algorithm info (must be a float in \
+                                    a string)"
+                            .to_owned())
+                    })?
+                    .parse()?,
+                port: port as u16,
+            })
+        } else {
+            Err(Error::Api("invalid multi-algorithm info object".to_owned()))
+        }
+    }
+
+    /// Gets the algorithm of the information.
+    pub fn get_algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+
+    /// Gets the name of the algorithm, as used by NiceHash.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    /// Gets the price currently paid for the algorithm, in BTCs per unit of speed per day.
+    ///
+    /// It is an average, so it is not rounded to satoshis.
+    pub fn get_paying(&self) -> f64 {
+        self.paying
+    }
+
+    /// Gets the stratum port for the algorithm.
+    pub fn get_port(&self) -> u16 {
+        self.port
+    }
+
+    /// Converts the `AlgoMultiInfo` to the JSON value used by the API.
+    #[cfg(feature = "serde")]
+    fn to_json(&self) -> Value {
+        ObjectBuilder::new()
+            .insert("algo", self.algorithm as u64)
+            .insert("name", self.name.as_str())
+            .insert("paying", float_to_json(self.paying))
+            .insert("port", self.port)
+            .build()
+    }
+}
+
 /// Order struct.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Order {
     id: u64,
     order_type: OrderType,
     limit_speed: f64,
     alive: bool,
-    price: f64,
+    price: Btc,
     workers: u64,
     algorithm: Algorithm,
     accepted_speed: f64,
+    btc_available: Option<Btc>,
 }
 
 impl Order {
@@ -344,6 +1299,12 @@ impl Order {
                 .ok_or_else(|| Error::Api("invalid order accepted speed".to_owned()))?
                 .parse()?;
 
+            let btc_available = if v.contains_key("btc_avail") {
+                Some(btc_field(&v, "btc_avail", "order")?)
+            } else {
+                None
+            };
+
             Ok(Order {
                 id: id,
                 order_type: order_type,
@@ -353,6 +1314,7 @@ impl Order {
                 workers: workers,
                 algorithm: algorithm,
                 accepted_speed: accepted_speed,
+                btc_available: btc_available,
             })
         } else {
             Err(Error::Api("invalid order object".to_owned()))
@@ -380,7 +1342,7 @@ impl Order {
     }
 
     /// Gets the price of the order.
-    pub fn get_price(&self) -> f64 {
+    pub fn get_price(&self) -> Btc {
         self.price
     }
 
@@ -398,12 +1360,50 @@ impl Order {
     pub fn get_accepted_speed(&self) -> f64 {
         self.accepted_speed
     }
+
+    /// Gets the speed limit of the order, with the unit given in the buy information of its
+    /// algorithm.
+    pub fn get_limit_hashrate(&self, buy_info: &AlgoBuyInfo) -> Result<Hashrate> {
+        Hashrate::from_native(self.limit_speed, buy_info)
+    }
+
+    /// Gets the current accepted speed of the order, with the unit given in the buy information
+    /// of its algorithm.
+    pub fn get_accepted_hashrate(&self, buy_info: &AlgoBuyInfo) -> Result<Hashrate> {
+        Hashrate::from_native(self.accepted_speed, buy_info)
+    }
+
+    /// Gets the remaining BTCs of the order.
+    ///
+    /// It is only available for the orders of the account, returned by `get_my_orders()`.
+    pub fn get_btc_available(&self) -> Option<Btc> {
+        self.btc_available
+    }
+
+    /// Converts the `Order` to the JSON value used by the API.
+    #[cfg(feature = "serde")]
+    fn to_json(&self) -> Value {
+        let order = ObjectBuilder::new()
+            .insert("id", self.id)
+            .insert("type", self.order_type as u64)
+            .insert("limit_speed", float_to_json(self.limit_speed))
+            .insert("alive", self.alive)
+            .insert("price", btc_to_json(self.price))
+            .insert("workers", self.workers)
+            .insert("algo", self.algorithm as u64)
+            .insert("accepted_speed", float_to_json(self.accepted_speed));
+        match self.btc_available {
+            Some(btc_available) => order.insert("btc_avail", btc_to_json(btc_available)).build(),
+            None => order.build(),
+        }
+    }
 }
 
 /// Buy information structure.
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct BuyInfo {
     down_time: Duration,
+    min_amount: Option<Btc>,
     scrypt: AlgoBuyInfo,
     sha256: AlgoBuyInfo,
     scrypt_nf: AlgoBuyInfo,
@@ -435,6 +1435,7 @@ impl BuyInfo {
     /// Creates a `BuyInfo` object from a JSON value.
     pub fn from_json(json: BTreeMap<String, Value>) -> Result<BuyInfo> {
         let mut down_time = None;
+        let mut min_amount = None;
         let mut scrypt = None;
         let mut sha256 = None;
         let mut scrypt_nf = None;
@@ -469,6 +1470,15 @@ impl BuyInfo {
                                 .to_owned())
                         })?);
                 }
+                "min_amount" => {
+                    min_amount = Some(value.as_str()
+                        .ok_or_else(|| {
+                            Error::Api("invalid `min_amount` in buy information (must be a BTC \
+                                        amount in a string)"
+                                .to_owned())
+                        })?
+                        .parse()?);
+                }
                 "algorithms" => {
                     if let Value::Array(arr) = value {
                         if arr.len() != 25 {
@@ -525,6 +1535,7 @@ impl BuyInfo {
             down_time: Duration::from_secs(down_time.ok_or_else(|| {
                     Error::Api("`down_time` not found in buy information structure".to_owned())
                 })?),
+            min_amount: min_amount,
             scrypt: scrypt.ok_or_else(|| {
                     Error::Api("Scrypt algorithm information not found in buy information \
                                 structure"
@@ -679,12 +1690,32 @@ impl BuyInfo {
     pub fn get_down_time(&self) -> Duration {
         self.down_time
     }
+
+    /// Gets the minimum amount of a new order, if the API reports it.
+    pub fn get_min_amount(&self) -> Option<Btc> {
+        self.min_amount
+    }
+
+    /// Converts the `BuyInfo` to the JSON value used by the API.
+    #[cfg(feature = "serde")]
+    fn to_json(&self) -> Value {
+        let mut json = ObjectBuilder::new().insert("down_time", self.down_time.as_secs());
+        if let Some(min_amount) = self.min_amount {
+            json = json.insert("min_amount", btc_to_json(min_amount));
+        }
+        json.insert("algorithms",
+                    Value::Array(Algorithm::all()
+                        .iter()
+                        .map(|alg| json_with_algorithm(self.get_buy_info_for(*alg).to_json(), *alg))
+                        .collect()))
+            .build()
+    }
 }
 
 /// Buy information for an algorithm.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, PartialEq)]
 pub struct AlgoBuyInfo {
-    down_step: f64,
+    down_step: Btc,
     min_limit: f64,
     speed_text: String,
     multi: f64,
@@ -702,51 +1733,65 @@ impl AlgoBuyInfo {
                                    integer)"
                         .to_owned())
                 })?)?;
-
-            Ok((algorithm,
-                AlgoBuyInfo {
-                down_step: buy_info.get("down_step")
-                    .ok_or_else(|| {
-                        Error::Api("`down_step` not found in buy information".to_owned())})?
-                    .as_str()
-                    .ok_or_else(|| {
-                        Error::Api("invalid `down_step` in stats (must be a float in a \
-                                       string)"
-                        .to_owned())})?
-                    .parse()?,
-                min_limit: buy_info.get("min_limit")
-                    .ok_or_else(|| {
-                        Error::Api("`min_limit` not found in buy information".to_owned())})?
-                    .as_str()
-                    .ok_or_else(|| {
-                        Error::Api("invalid `min_limit` in stats (must be a float in a \
-                                       string)"
-                        .to_owned())})?
-                    .parse()?,
-                speed_text: buy_info.get("speed_text")
-                    .ok_or_else(|| {
-                        Error::Api("`speed_text` not found in buy information".to_owned())})?
-                    .as_str()
-                    .ok_or_else(|| {
-                        Error::Api("invalid `speed_text` in stats (must be a string)"
-                        .to_owned())})?
-                    .to_owned(),
-                multi: buy_info.get("multi")
-                    .ok_or_else(|| {
-                        Error::Api("`multi` not found in buy information".to_owned())})?
-                    .as_str()
-                    .ok_or_else(|| {
-                        Error::Api("invalid `multi` in stats (must be a float in a string)"
-                        .to_owned())})?
-                    .parse()?,
-            }))
+            Ok((algorithm, AlgoBuyInfo::from_json_fields(&buy_info)?))
         } else {
             Err(Error::Api("invalid buy information object".to_owned()))
         }
     }
 
+    /// Creates an `AlgoBuyInfo` from the fields of a JSON value, ignoring the algorithm.
+    fn from_json_fields(buy_info: &BTreeMap<String, Value>) -> Result<AlgoBuyInfo> {
+        Ok(AlgoBuyInfo {
+            down_step: buy_info.get("down_step")
+                .ok_or_else(|| {
+                    Error::Api("`down_step` not found in buy information".to_owned())})?
+                .as_str()
+                .ok_or_else(|| {
+                    Error::Api("invalid `down_step` in stats (must be a BTC amount in a \
+                                   string)"
+                    .to_owned())})?
+                .parse()?,
+            min_limit: buy_info.get("min_limit")
+                .ok_or_else(|| {
+                    Error::Api("`min_limit` not found in buy information".to_owned())})?
+                .as_str()
+                .ok_or_else(|| {
+                    Error::Api("invalid `min_limit` in stats (must be a float in a \
+                                   string)"
+                    .to_owned())})?
+                .parse()?,
+            speed_text: buy_info.get("speed_text")
+                .ok_or_else(|| {
+                    Error::Api("`speed_text` not found in buy information".to_owned())})?
+                .as_str()
+                .ok_or_else(|| {
+                    Error::Api("invalid `speed_text` in stats (must be a string)"
+                    .to_owned())})?
+                .to_owned(),
+            multi: buy_info.get("multi")
+                .ok_or_else(|| {
+                    Error::Api("`multi` not found in buy information".to_owned())})?
+                .as_str()
+                .ok_or_else(|| {
+                    Error::Api("invalid `multi` in stats (must be a float in a string)"
+                    .to_owned())})?
+                .parse()?,
+        })
+    }
+
+    /// Converts the `AlgoBuyInfo` to the JSON value used by the API, without the algorithm.
+    #[cfg(feature = "serde")]
+    fn to_json(&self) -> Value {
+        ObjectBuilder::new()
+            .insert("down_step", btc_to_json(self.down_step))
+            .insert("min_limit", float_to_json(self.min_limit))
+            .insert("speed_text", self.speed_text.as_str())
+            .insert("multi", float_to_json(self.multi))
+            .build()
+    }
+
     /// Gets the step for price downgrade.
-    pub fn get_down_step(&self) -> f64 {
+    pub fn get_down_step(&self) -> Btc {
         self.down_step
     }
 
@@ -760,12 +1805,27 @@ impl AlgoBuyInfo {
         &self.speed_text
     }
 
+    /// Gets the unit of the speed of the algorithm.
+    ///
+    /// All the speeds of the algorithm in the API, such as order limits and accepted speeds, use
+    /// this unit.
+    pub fn get_speed_unit(&self) -> Result<HashrateUnit> {
+        self.speed_text.parse()
+    }
+
     /// Gets the multiplier of the algorithm.
     pub fn get_multiplier(&self) -> f64 {
         self.multi
     }
 }
 
+/// Maximum length of the pool usernames accepted by `PoolInfo::validate()` if no other maximum is
+/// given, in bytes.
+///
+/// NiceHash does not document a maximum length, so clients can change it with
+/// `ClientBuilder::max_pool_username_length()`.
+pub const DEFAULT_MAX_POOL_USERNAME_LENGTH: usize = 128;
+
 /// Pool information.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PoolInfo {
@@ -779,24 +1839,357 @@ pub struct PoolInfo {
     pub password: String,
 }
 
+impl PoolInfo {
+    /// Creates a `PoolInfo` from a JSON value with the pool parameters of the API.
+    pub fn from_json(json: Value) -> Result<PoolInfo> {
+        if let Value::Object(pool) = json {
+            let port = pool.get("pool_port")
+                .ok_or_else(|| Error::Api("`pool_port` not found in pool information".to_owned()))?
+                .as_u64()
+                .ok_or_else(|| {
+                    Error::Api("invalid `pool_port` in pool information (must be an unsigned \
+                                integer)"
+                        .to_owned())
+                })?;
+            if port > u16::max_value() as u64 {
+                return Err(Error::Api(format!("invalid port {} in pool information", port)));
+            }
+
+            Ok(PoolInfo {
+                host: string_field(&pool, "pool_host", "pool information")?,
+                port: port as u16,
+                username: string_field(&pool, "pool_user", "pool information")?,
+                password: string_field(&pool, "pool_pass", "pool information")?,
+            })
+        } else {
+            Err(Error::Api("invalid pool information object".to_owned()))
+        }
+    }
+
+    /// Checks that the pool can be used in a new order.
+    ///
+    /// The host must not be empty, the port must not be `0` and the username must not be longer
+    /// than the given maximum length, or `DEFAULT_MAX_POOL_USERNAME_LENGTH` if none is given.
+    pub fn validate(&self, max_username_length: Option<usize>) -> Result<()> {
+        if self.host.trim().is_empty() {
+            return Err(Error::InvalidPoolHost("the pool host is empty".to_owned()));
+        }
+        if self.port == 0 {
+            return Err(Error::InvalidPoolPort(format!("invalid port 0 for pool `{}`",
+                                                      self.host)));
+        }
+        let max_length = max_username_length.unwrap_or(DEFAULT_MAX_POOL_USERNAME_LENGTH);
+        if self.username.len() > max_length {
+            return Err(Error::PoolUsernameTooLong(format!("the pool username is {} bytes long \
+                                                           (maximum {})",
+                                                          self.username.len(),
+                                                          max_length)));
+        }
+        Ok(())
+    }
+
+    /// Converts the `PoolInfo` to a JSON value with the pool parameters of the API.
+    #[cfg(feature = "serde")]
+    fn to_json(&self) -> Value {
+        ObjectBuilder::new()
+            .insert("pool_host", self.host.as_str())
+            .insert("pool_port", self.port)
+            .insert("pool_user", self.username.as_str())
+            .insert("pool_pass", self.password.as_str())
+            .build()
+    }
+}
+
 /// New order information.
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct NewOrder {
     /// Algorithm for the new order.
     pub algorithm: Algorithm,
     /// Amount of BTC used to pay the order.
-    pub amount: f64,
+    pub amount: Btc,
     /// Price of the hashing.
-    pub price: f64,
-    /// Hashing limit.
-    pub limit: Option<f64>,
+    pub price: Btc,
+    /// Hashing limit, in any unit compatible with the algorithm.
+    ///
+    /// It is converted to the native unit of the algorithm when creating the order. Limits parsed
+    /// from the parameters of the API are in `HashrateUnit::Native`.
+    pub limit: Option<Hashrate>,
+}
+
+impl NewOrder {
+    /// Creates a `NewOrder` from a JSON value with the order parameters of the API.
+    ///
+    /// The limit is a bare speed in the native unit of the algorithm, such as `1.5`, and a limit
+    /// of `0` means that the order has no limit.
+    pub fn from_json(json: Value) -> Result<NewOrder> {
+        if let Value::Object(order) = json {
+            let algorithm = Algorithm::from_u64(order.get("algo")
+                .ok_or_else(|| Error::Api("`algo` not found in new order".to_owned()))?
+                .as_u64()
+                .ok_or_else(|| {
+                    Error::Api("invalid algorithm in new order (must be an unsigned integer)"
+                        .to_owned())
+                })?)?;
+            let limit = string_field(&order, "limit", "new order")?;
+            let limit = match limit.parse::<f64>() {
+                Ok(limit) if limit <= 0.0 => None,
+                _ => Some(limit.parse()?),
+            };
+
+            Ok(NewOrder {
+                algorithm: algorithm,
+                amount: btc_field(&order, "amount", "new order")?,
+                price: btc_field(&order, "price", "new order")?,
+                limit: limit,
+            })
+        } else {
+            Err(Error::Api("invalid new order object".to_owned()))
+        }
+    }
+
+    /// Checks the order against the buy information, before sending it to the API.
+    ///
+    /// The amount must be at least the minimum amount of the buy information, if the API reports
+    /// it, and the limit, if any, must be at least the minimum limit of the algorithm. It also
+    /// fails if the limit cannot be converted to the native unit of the algorithm. The pool of the
+    /// order can be checked with `PoolInfo::validate()`.
+    pub fn validate(&self, buy_info: &BuyInfo) -> Result<()> {
+        if let Some(min_amount) = buy_info.get_min_amount() {
+            if self.amount < min_amount {
+                return Err(Error::AmountBelowMinimum(format!("order amount {} BTC is below \
+                                                              the minimum of {} BTC",
+                                                             self.amount,
+                                                             min_amount)));
+            }
+        }
+        if let Some(limit) = self.limit {
+            let algo_buy_info = buy_info.get_buy_info_for(self.algorithm);
+            let min_limit = algo_buy_info.get_min_limit();
+            let limit = limit.to_native(algo_buy_info)?;
+            if limit < min_limit {
+                return Err(Error::LimitBelowMinimum(format!("order limit {} is below the \
+                                                             minimum limit {} of {:?}",
+                                                            limit,
+                                                            min_limit,
+                                                            self.algorithm)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Gets the order with its limit, if any, in the native unit of the algorithm, as sent to the
+    /// API.
+    ///
+    /// It fails if the limit cannot be converted to the native unit of the algorithm.
+    pub fn with_native_limit(&self, buy_info: &BuyInfo) -> Result<NewOrder> {
+        let limit = match self.limit {
+            Some(limit) => {
+                let native = limit.to_native(buy_info.get_buy_info_for(self.algorithm))?;
+                Some(Hashrate::new(native, HashrateUnit::Native))
+            }
+            None => None,
+        };
+        Ok(NewOrder {
+            algorithm: self.algorithm,
+            amount: self.amount,
+            price: self.price,
+            limit: limit,
+        })
+    }
+
+    /// Converts the `NewOrder` to a JSON value with the order parameters of the API.
+    ///
+    /// It fails if the limit is not in the native unit of the algorithm, since the API only
+    /// accepts bare speeds. It can be converted with `with_native_limit()`.
+    #[cfg(feature = "serde")]
+    fn to_json(&self) -> Result<Value> {
+        let limit = match self.limit {
+            Some(limit) => float_to_json(limit.value_in(HashrateUnit::Native)?),
+            None => Value::String("0".to_owned()),
+        };
+        Ok(ObjectBuilder::new()
+            .insert("algo", self.algorithm as u64)
+            .insert("amount", btc_to_json(self.amount))
+            .insert("price", btc_to_json(self.price))
+            .insert("limit", limit)
+            .build())
+    }
 }
 
 /// Account balance.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, PartialEq)]
 pub struct Balance {
     /// Confirmed balance.
-    pub confirmed: f64,
+    pub confirmed: Btc,
     /// Pending balance.
-    pub pending: f64,
+    pub pending: Btc,
+}
+
+impl Balance {
+    /// Creates a `Balance` from a JSON value.
+    pub fn from_json(json: Value) -> Result<Balance> {
+        if let Value::Object(balance) = json {
+            Ok(Balance {
+                confirmed: btc_field(&balance, "balance_confirmed", "balance")?,
+                pending: btc_field(&balance, "balance_pending", "balance")?,
+            })
+        } else {
+            Err(Error::Api("invalid balance object".to_owned()))
+        }
+    }
+
+    /// Converts the `Balance` to the JSON value used by the API.
+    #[cfg(feature = "serde")]
+    fn to_json(&self) -> Value {
+        ObjectBuilder::new()
+            .insert("balance_confirmed", btc_to_json(self.confirmed))
+            .insert("balance_pending", btc_to_json(self.pending))
+            .build()
+    }
+}
+
+/// Gets a BTC amount in a string from the given field of a JSON object.
+///
+/// The `object` is the name of the object used in the error messages.
+fn btc_field(json: &BTreeMap<String, Value>, key: &str, object: &str) -> Result<Btc> {
+    json.get(key)
+        .ok_or_else(|| Error::Api(format!("`{}` not found in {}", key, object)))?
+        .as_str()
+        .ok_or_else(|| {
+            Error::Api(format!("invalid `{}` in {} (must be a BTC amount in a string)",
+                               key,
+                               object))
+        })?
+        .parse()
+}
+
+/// Gets a string from the given field of a JSON object.
+///
+/// The `object` is the name of the object used in the error messages.
+fn string_field(json: &BTreeMap<String, Value>, key: &str, object: &str) -> Result<String> {
+    Ok(json.get(key)
+        .ok_or_else(|| Error::Api(format!("`{}` not found in {}", key, object)))?
+        .as_str()
+        .ok_or_else(|| Error::Api(format!("invalid `{}` in {} (must be a string)", key, object)))?
+        .to_owned())
+}
+
+/// Converts a float to the string representation used by the API.
+///
+/// The shortest representation that parses back to the same float is used, so that the values
+/// round-trip exactly.
+#[cfg(feature = "serde")]
+fn float_to_json(float: f64) -> Value {
+    Value::String(format!("{}", float))
+}
+
+/// Converts a BTC amount to the 8 decimal string representation used by the API.
+#[cfg(feature = "serde")]
+fn btc_to_json(btc: Btc) -> Value {
+    Value::String(btc.to_string())
+}
+
+/// Adds the `algo` field with the given algorithm to a JSON object.
+#[cfg(feature = "serde")]
+fn json_with_algorithm(mut json: Value, algorithm: Algorithm) -> Value {
+    if let Value::Object(ref mut object) = json {
+        let _ = object.insert("algo".to_owned(), Value::U64(algorithm as u64));
+    }
+    json
+}
+
+/// Serde support for the API types.
+///
+/// The types are serialized to the same JSON representation used by the API, with the `to_json()`
+/// methods, and they are deserialized with the same parsers used for the API responses.
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use std::error::Error as StdError;
+    use std::result::Result as StdResult;
+
+    use serde::{Serialize, Serializer, Deserialize, Deserializer};
+    use serde::ser::Error as SerError;
+    use serde::de::Error as DeError;
+    use serde_json::value::Value;
+
+    use error::{Result, Error};
+    use super::{GlobalStats, AlgoStat, ProviderStats, ProviderAlgoStat, ProviderStatsEx,
+                ProviderCurrentStat, ProviderHistory, ProviderSample, Payment, ProviderWorker,
+                MultiAlgoInfo, AlgoMultiInfo, Order, BuyInfo, AlgoBuyInfo, PoolInfo, NewOrder,
+                Balance};
+
+    /// Implements `Serialize` and `Deserialize` for a type, given the expression that parses it
+    /// from the deserialized JSON value.
+    macro_rules! impl_serde {
+        ($ty:ident, $json:ident => $from_json:expr) => {
+            impl Serialize for $ty {
+                fn serialize<S: Serializer>(&self, serializer: &mut S) -> StdResult<(), S::Error> {
+                    self.to_json().serialize(serializer)
+                }
+            }
+
+            impl_deserialize!($ty, $json => $from_json);
+        }
+    }
+
+    /// Implements `Deserialize` for a type, given the expression that parses it from the
+    /// deserialized JSON value.
+    macro_rules! impl_deserialize {
+        ($ty:ident, $json:ident => $from_json:expr) => {
+            impl Deserialize for $ty {
+                fn deserialize<D: Deserializer>(deserializer: &mut D) -> StdResult<$ty, D::Error> {
+                    let $json = Value::deserialize(deserializer)?;
+                    let result: Result<$ty> = $from_json;
+                    result.map_err(|e| DeError::custom(e.description()))
+                }
+            }
+        }
+    }
+
+    impl_serde!(GlobalStats, json => match json {
+        Value::Array(stats) => GlobalStats::from_json(stats),
+        _ => Err(Error::Api("invalid global stats, expected array".to_owned())),
+    });
+    impl_serde!(AlgoStat, json => match json {
+        Value::Object(stat) => AlgoStat::from_json_fields(&stat),
+        _ => Err(Error::Api("invalid stats object".to_owned())),
+    });
+    impl_serde!(ProviderStats, json => match json {
+        Value::Array(stats) => ProviderStats::from_json(stats),
+        _ => Err(Error::Api("invalid provider stats, expected array".to_owned())),
+    });
+    impl_serde!(ProviderAlgoStat, json => ProviderAlgoStat::from_json(json));
+    impl_serde!(ProviderStatsEx, json => match json {
+        Value::Object(stats) => ProviderStatsEx::from_json(stats),
+        _ => Err(Error::Api("invalid provider stats object".to_owned())),
+    });
+    impl_serde!(ProviderCurrentStat, json => ProviderCurrentStat::from_json(json));
+    impl_serde!(ProviderHistory, json => ProviderHistory::from_json(json));
+    impl_serde!(ProviderSample, json => ProviderSample::from_json(&json));
+    impl_serde!(Payment, json => Payment::from_json(json));
+    impl_serde!(ProviderWorker, json => ProviderWorker::from_json(json, None));
+    impl_serde!(MultiAlgoInfo, json => match json {
+        Value::Array(info) => MultiAlgoInfo::from_json(info),
+        _ => Err(Error::Api("invalid multi-algorithm info, expected array".to_owned())),
+    });
+    impl_serde!(AlgoMultiInfo, json => AlgoMultiInfo::from_json(json));
+    impl_serde!(Order, json => Order::from_json(json));
+    impl_serde!(BuyInfo, json => match json {
+        Value::Object(info) => BuyInfo::from_json(info),
+        _ => Err(Error::Api("invalid buy information object".to_owned())),
+    });
+    impl_serde!(AlgoBuyInfo, json => match json {
+        Value::Object(info) => AlgoBuyInfo::from_json_fields(&info),
+        _ => Err(Error::Api("invalid buy information object".to_owned())),
+    });
+    impl_serde!(PoolInfo, json => PoolInfo::from_json(json));
+    impl_deserialize!(NewOrder, json => NewOrder::from_json(json));
+    impl_serde!(Balance, json => Balance::from_json(json));
+
+    /// The limit of the order must be in the native unit of the algorithm to be serialized.
+    impl Serialize for NewOrder {
+        fn serialize<S: Serializer>(&self, serializer: &mut S) -> StdResult<(), S::Error> {
+            self.to_json().map_err(|e| SerError::custom(e.description()))?.serialize(serializer)
+        }
+    }
 }
@@ -0,0 +1,85 @@
+extern crate nicehash;
+
+use nicehash::{Btc, ORDER_FEE, calculate_withdrawal_fee, calculate_service_fee};
+
+#[test]
+fn it_btc_parse() {
+    assert_eq!(Btc::from_satoshis(10_000), "0.00010000".parse().unwrap());
+    assert_eq!(Btc::from_satoshis(10_000), "0.0001".parse().unwrap());
+    assert_eq!(Btc::from_satoshis(-100_000), "-0.001".parse().unwrap());
+    assert_eq!(Btc::from_satoshis(200_000_000), "2".parse().unwrap());
+    assert_eq!(Btc::from_satoshis(50_000_000), ".5".parse().unwrap());
+    assert_eq!(Btc::from_satoshis(1), "0.00000001".parse().unwrap());
+
+    assert!("0.000000001".parse::<Btc>().is_err());
+    assert!("".parse::<Btc>().is_err());
+    assert!(".".parse::<Btc>().is_err());
+    assert!("1e-8".parse::<Btc>().is_err());
+    assert!("+1".parse::<Btc>().is_err());
+    assert!("0.5 BTC".parse::<Btc>().is_err());
+    assert!("99999999999999999999".parse::<Btc>().is_err());
+    match "abc".parse::<Btc>() {
+        Err(nicehash::error::Error::ParseBtc(_)) => {}
+        r => panic!("unexpected result: {:?}", r),
+    }
+}
+
+#[test]
+fn it_btc_display() {
+    assert_eq!("0.00010000", ORDER_FEE.to_string());
+    assert_eq!("2.50000000", Btc::from_satoshis(250_000_000).to_string());
+    assert_eq!("-0.00100000", Btc::from_satoshis(-100_000).to_string());
+    assert_eq!("0.00000000", Btc::zero().to_string());
+
+    let btc: Btc = "0.12345678".parse().unwrap();
+    assert_eq!(btc, btc.to_string().parse().unwrap());
+}
+
+#[test]
+fn it_btc_arithmetic() {
+    let a: Btc = "0.1".parse().unwrap();
+    let b: Btc = "0.2".parse().unwrap();
+    assert_eq!(Btc::from_satoshis(30_000_000), a + b);
+    assert_eq!(Btc::from_satoshis(-10_000_000), a - b);
+    assert_eq!(Btc::from_satoshis(10_000_000), -(a - b));
+    assert_eq!(Some(Btc::from_satoshis(30_000_000)), a.checked_mul(3));
+    assert_eq!(Some(Btc::from_satoshis(3_000_000)), a.checked_mul_rate(0.3));
+    assert_eq!(None, Btc::from_satoshis(i64::max_value()).checked_add(Btc::from_satoshis(1)));
+    assert_eq!(None, Btc::from_satoshis(i64::min_value()).checked_sub(Btc::from_satoshis(1)));
+    assert_eq!(None, Btc::from_satoshis(i64::max_value()).checked_mul(2));
+    assert!(a < b);
+
+    let mut sum = Btc::zero();
+    for _ in 0..10 {
+        sum += a;
+    }
+    assert_eq!(Btc::from_satoshis(100_000_000), sum);
+    sum -= b;
+    assert_eq!(Btc::from_satoshis(80_000_000), sum);
+
+    assert_eq!(Some(Btc::from_satoshis(12_345_679)), Btc::from_f64(0.123456789));
+    assert_eq!(None, Btc::from_f64(::std::f64::NAN));
+    assert_eq!(0.5, Btc::from_satoshis(50_000_000).to_f64());
+}
+
+#[test]
+fn it_fees() {
+    assert_eq!(Some(Btc::from_satoshis(50_000)),
+               calculate_withdrawal_fee("0.5".parse().unwrap()));
+    assert_eq!(Some(Btc::from_satoshis(100_000)),
+               calculate_withdrawal_fee("1".parse().unwrap()));
+    assert_eq!(Some(Btc::from_satoshis(300_000)),
+               calculate_service_fee("0.1".parse().unwrap()));
+    assert_eq!(Some(Btc::from_satoshis(3)), calculate_service_fee(Btc::from_satoshis(100)));
+    assert_eq!(Some(Btc::from_satoshis(2)), calculate_service_fee(Btc::from_satoshis(50)));
+    assert_eq!(Some(Btc::from_satoshis(276_701_161_105_643_274)),
+               calculate_service_fee(Btc::from_satoshis(i64::max_value())));
+    assert_eq!(None, calculate_service_fee(Btc::from_satoshis(-1)));
+    assert_eq!(None, calculate_withdrawal_fee(Btc::from_satoshis(-1)));
+
+    assert_eq!(Some(Btc::from_satoshis(-2)), Btc::from_satoshis(-50).checked_mul_per_mille(30));
+    assert_eq!(None, Btc::from_satoshis(i64::max_value()).checked_mul_per_mille(2000));
+    assert_eq!(None, Btc::from_satoshis(i64::min_value()).checked_abs());
+    assert_eq!(None, Btc::from_satoshis(i64::min_value()).checked_neg());
+    assert_eq!(Some(Btc::from_satoshis(5)), Btc::from_satoshis(-5).checked_neg());
+}
@@ -0,0 +1,58 @@
+//! Helpers shared by the integration tests.
+
+#![allow(dead_code)]
+
+use nicehash::{Client, AuthenticatedClient, Credentials, Location, Algorithm, Btc};
+use nicehash::types::{NewOrder, PoolInfo};
+
+#[cfg(feature = "mock")]
+use nicehash::mock::MockServer;
+
+pub const TEST_API_ID: u64 = 70022;
+pub const TEST_API_KEY: &'static str = "ea454eef-ef74-42da-a2ed-b971bb212718";
+pub const TEST_READ_API_KEY: &'static str = "fd1baeda-e66f-4ebe-aa27-c791ae87ba86";
+pub const TEST_BTC_ADDRESS: &'static str = "1P5PNW6Wd53QiZLdCs9EXNHmuPTX3rD6hW";
+
+/// Parses a BTC amount.
+pub fn btc(amount: &str) -> Btc {
+    amount.parse().unwrap()
+}
+
+/// Gets the pool information used in the tests.
+pub fn test_pool() -> PoolInfo {
+    PoolInfo {
+        host: "my.test.pool".to_owned(),
+        port: 5650,
+        username: "TestUser".to_owned(),
+        password: "test_password".to_owned(),
+    }
+}
+
+/// Creates a new X11 order without a speed limit.
+pub fn new_order(amount: &str, price: &str) -> NewOrder {
+    NewOrder {
+        algorithm: Algorithm::X11,
+        amount: btc(amount),
+        price: btc(price),
+        limit: None,
+    }
+}
+
+/// Authenticates the given client with the full access key of the test account.
+pub fn authenticated(client: &Client) -> AuthenticatedClient {
+    client.authenticated(Credentials::new(TEST_API_ID, TEST_API_KEY))
+}
+
+/// Creates the given order in Europe with the test pool, and returns its ID.
+pub fn create_order(client: &AuthenticatedClient, order: NewOrder) -> u64 {
+    client.create_order(Location::Europe, order, test_pool(), None).unwrap()
+}
+
+/// Starts a mock server with the test account and a confirmed balance of 1 BTC.
+#[cfg(feature = "mock")]
+pub fn mock_server() -> MockServer {
+    let server = MockServer::start().unwrap();
+    server.add_account(TEST_API_ID, TEST_API_KEY, TEST_READ_API_KEY);
+    server.set_balance(TEST_API_ID, btc("1.0"), btc("0.0"));
+    server
+}
@@ -0,0 +1,115 @@
+#![cfg(feature = "mock")]
+
+extern crate nicehash;
+
+mod common;
+
+use std::time::Duration;
+
+use nicehash::{Location, Algorithm, Btc, DAY};
+use nicehash::cost::{OrderCost, RefillEstimate};
+use nicehash::hashrate::{Hashrate, HashrateUnit};
+use nicehash::mock::{MockServer, MockOrder};
+use nicehash::types::NewOrder;
+
+use common::{btc, new_order, authenticated, create_order, mock_server};
+
+#[test]
+fn it_order_cost() {
+    let server = MockServer::start().unwrap();
+    let client = server.client().unwrap();
+    let buy_info = client.get_buy_info().unwrap();
+    let x11 = buy_info.get_buy_info_for(Algorithm::X11);
+
+    let mut order = NewOrder {
+        limit: Some(Hashrate::new(500.0, HashrateUnit::KiloHash)),
+        ..new_order("0.1", "0.05")
+    };
+    let cost = OrderCost::new(&order, x11).unwrap();
+    assert_eq!(btc("0.1"), cost.get_amount());
+    assert_eq!(btc("0.0001"), cost.get_order_fee());
+    assert_eq!(btc("0.003"), cost.get_service_fee());
+    assert_eq!(btc("0.1031"), cost.get_total());
+    assert_eq!(Some(btc("0.025")), cost.get_btc_per_day());
+    assert_eq!(Some(Duration::from_secs(4 * DAY)), cost.get_duration());
+
+    order.limit = None;
+    let cost = OrderCost::new(&order, x11).unwrap();
+    assert_eq!(btc("0.1031"), cost.get_total());
+    assert_eq!(None, cost.get_btc_per_day());
+    assert_eq!(None, cost.get_duration());
+
+    let cost = OrderCost::at_speed(&order, x11, Hashrate::new(1.0, HashrateUnit::MegaHash))
+        .unwrap();
+    assert_eq!(Some(Duration::from_secs(2 * DAY)), cost.get_duration());
+    let cost = OrderCost::at_speed(&order, x11, Hashrate::new(0.0, HashrateUnit::MegaHash))
+        .unwrap();
+    assert_eq!(None, cost.get_duration());
+    assert!(OrderCost::at_speed(&order, x11, Hashrate::new(1.0, HashrateUnit::Solution))
+        .is_err());
+}
+
+#[test]
+fn it_order_cost_overflow() {
+    let server = MockServer::start().unwrap();
+    let client = server.client().unwrap();
+    let buy_info = client.get_buy_info().unwrap();
+    let x11 = buy_info.get_buy_info_for(Algorithm::X11);
+
+    let order = NewOrder {
+        price: Btc::from_satoshis(i64::max_value() / 2),
+        limit: Some(Hashrate::new(1000.0, HashrateUnit::TeraHash)),
+        ..new_order("0.1", "0.05")
+    };
+    match OrderCost::new(&order, x11) {
+        Err(nicehash::error::Error::Overflow(_)) => {}
+        r => panic!("unexpected result: {:?}", r),
+    }
+
+    let order = NewOrder {
+        amount: Btc::from_satoshis(i64::max_value()),
+        ..new_order("0.1", "0.05")
+    };
+    match OrderCost::new(&order, x11) {
+        Err(nicehash::error::Error::Overflow(_)) => {}
+        r => panic!("unexpected result: {:?}", r),
+    }
+}
+
+#[test]
+fn it_refill_estimate() {
+    let server = mock_server();
+    let client = server.client().unwrap();
+    let buy_info = client.get_buy_info().unwrap();
+    let x11 = buy_info.get_buy_info_for(Algorithm::X11);
+
+    let authenticated = authenticated(&client);
+    let id = create_order(&authenticated, new_order("0.05", "0.05"));
+    let _ = server.update_order(id, |order| order.accepted_speed = 1.0);
+    let orders = authenticated.get_my_orders(Location::Europe, Algorithm::X11).unwrap();
+
+    let estimate = RefillEstimate::new(&orders[0], x11, Duration::from_secs(2 * DAY))
+        .unwrap();
+    assert_eq!(btc("0.05"), estimate.get_btc_per_day());
+    assert_eq!(Some(Duration::from_secs(DAY)), estimate.get_remaining_time());
+    assert_eq!(btc("0.1"), estimate.get_needed());
+    assert_eq!(btc("0.05"), estimate.get_amount());
+    assert_eq!(btc("0.0015"), estimate.get_service_fee());
+    assert_eq!(btc("0.0515"), estimate.get_cost());
+
+    let estimate = RefillEstimate::new(&orders[0], x11, Duration::from_secs(12 * 60 * 60))
+        .unwrap();
+    assert_eq!(btc("0.025"), estimate.get_needed());
+    assert!(estimate.get_amount().is_zero());
+    assert!(estimate.get_cost().is_zero());
+
+    // The remaining BTCs of the orders of other accounts are unknown.
+    let mut other = MockOrder::new(Location::Europe, Algorithm::X11, btc("0.05"));
+    other.accepted_speed = 2.0;
+    let other = server.add_order(other);
+    let orders = client.get_orders(Location::Europe, Algorithm::X11).unwrap();
+    let other = orders.iter().find(|order| order.get_id() == other).unwrap();
+    let estimate = RefillEstimate::new(other, x11, Duration::from_secs(DAY)).unwrap();
+    assert_eq!(None, estimate.get_remaining_time());
+    assert_eq!(btc("0.1"), estimate.get_amount());
+}
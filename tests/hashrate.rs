@@ -0,0 +1,102 @@
+#![cfg(feature = "mock")]
+
+extern crate nicehash;
+
+mod common;
+
+use nicehash::{Location, Algorithm, Btc};
+use nicehash::hashrate::{Hashrate, HashrateUnit};
+use nicehash::mock::MockOrder;
+use nicehash::types::NewOrder;
+
+use common::{TEST_API_ID, TEST_API_KEY, btc, test_pool, mock_server};
+
+#[test]
+fn it_hashrate_parse() {
+    let hashrate: Hashrate = "1.5 GH/s".parse().unwrap();
+    assert_eq!(Hashrate::new(1.5, HashrateUnit::GigaHash), hashrate);
+    assert_eq!("1.5 GH/s", hashrate.to_string());
+    assert_eq!(Hashrate::new(300.0, HashrateUnit::Solution),
+               "300Sol/s".parse().unwrap());
+    assert_eq!(HashrateUnit::KiloHash, "kH".parse().unwrap());
+    assert_eq!(Hashrate::new(1000.0, HashrateUnit::GigaHash),
+               "1e3 GH/s".parse().unwrap());
+    assert_eq!(Hashrate::new(0.0025, HashrateUnit::KiloSolution),
+               "2.5E-3kSol/s".parse().unwrap());
+    assert!("-1 H".parse::<Hashrate>().is_err());
+    assert!("NaN MH/s".parse::<Hashrate>().is_err());
+    assert_eq!(Hashrate::new(0.0, HashrateUnit::Hash), "-0 H".parse().unwrap());
+    let native: Hashrate = "1.5".parse().unwrap();
+    assert_eq!(Hashrate::new(1.5, HashrateUnit::Native), native);
+    assert_eq!("1.5", native.to_string());
+    assert!(native.value_in(HashrateUnit::GigaHash).is_err());
+    assert!("fast GH/s".parse::<Hashrate>().is_err());
+    assert!("1.5 XH/s".parse::<Hashrate>().is_err());
+    assert!("GH/s".parse::<Hashrate>().is_err());
+    assert!("1e GH/s".parse::<Hashrate>().is_err());
+
+    assert_eq!(1500.0, hashrate.value_in(HashrateUnit::MegaHash).unwrap());
+    let converted = hashrate.convert_to(HashrateUnit::TeraHash).unwrap();
+    assert_eq!(HashrateUnit::TeraHash, converted.get_unit());
+    assert!((converted.get_value() - 0.0015).abs() < 1e-12);
+    assert!(hashrate.value_in(HashrateUnit::Solution).is_err());
+}
+
+#[test]
+fn it_hashrate_native_units() {
+    let server = mock_server();
+    let mut order = MockOrder::new(Location::Europe, Algorithm::SHA256, btc("0.01"));
+    order.limit_speed = 2.0;
+    order.accepted_speed = 1.5;
+    let _ = server.add_order(order);
+    let client = server.client().unwrap();
+    let buy_info = client.get_buy_info().unwrap();
+
+    let sha256 = buy_info.get_buy_info_for(Algorithm::SHA256);
+    assert_eq!(HashrateUnit::TeraHash, sha256.get_speed_unit().unwrap());
+    let equihash = buy_info.get_buy_info_for(Algorithm::Equihash);
+    assert_eq!(HashrateUnit::Solution, equihash.get_speed_unit().unwrap());
+
+    let orders = client.get_orders(Location::Europe, Algorithm::SHA256).unwrap();
+    assert_eq!(Hashrate::new(2.0, HashrateUnit::TeraHash),
+               orders[0].get_limit_hashrate(sha256).unwrap());
+    assert_eq!(1500.0,
+               orders[0]
+                   .get_accepted_hashrate(sha256)
+                   .unwrap()
+                   .value_in(HashrateUnit::GigaHash)
+                   .unwrap());
+
+    let pool = test_pool();
+    let new_order = |limit| {
+        NewOrder {
+            algorithm: Algorithm::SHA256,
+            amount: Btc::from_satoshis(10_000_000),
+            price: Btc::from_satoshis(1_000_000),
+            limit: Some(limit),
+        }
+    };
+    let id = client.create_order(TEST_API_ID,
+                      TEST_API_KEY,
+                      Location::Europe,
+                      new_order(Hashrate::new(500.0, HashrateUnit::GigaHash)),
+                      pool.clone(),
+                      None)
+        .unwrap();
+    assert_eq!(0.5, server.get_order(id).unwrap().limit_speed);
+    match client.create_order(TEST_API_ID,
+                              TEST_API_KEY,
+                              Location::Europe,
+                              new_order(Hashrate::new(500.0, HashrateUnit::Solution)),
+                              pool,
+                              None) {
+        Err(nicehash::error::Error::ParseHashrate(_)) => {}
+        r => panic!("unexpected result: {:?}", r),
+    }
+}
+
+#[test]
+#[should_panic]
+fn it_hashrate_new_negative() {
+    let _ = Hashrate::new(-1.0, HashrateUnit::Hash);
+}
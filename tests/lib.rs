@@ -1,55 +1,586 @@
 extern crate nicehash;
+extern crate hyper;
 
-use std::time::Duration;
+mod common;
+
+use std::time::{Duration, Instant};
 use std::error::Error;
+use std::io::{self, Cursor, Read};
+use std::net::TcpListener;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use nicehash::{ClientBuilder, RetryPolicy, RateLimit, RateLimiter, Location, Algorithm};
+use nicehash::transport::{Transport, Url};
+use nicehash::types::NewOrder;
+use nicehash::error::Result;
+
+use common::{TEST_API_ID, TEST_API_KEY, TEST_BTC_ADDRESS, btc, test_pool};
+
+#[cfg(feature = "mock")]
 use std::f64;
+#[cfg(feature = "mock")]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[cfg(feature = "mock")]
+use nicehash::{Client, Credentials, KeyAccess};
+#[cfg(feature = "mock")]
+use nicehash::hashrate::{Hashrate, HashrateUnit};
+#[cfg(feature = "mock")]
+use nicehash::mock::{MockServer, MockOrder, MockProviderStat, MockPayment, MockWorker};
 
-use nicehash::{Client, Location, Algorithm};
-use nicehash::types::{NewOrder, PoolInfo};
+#[cfg(feature = "mock")]
+use common::TEST_READ_API_KEY;
 
-const TEST_API_ID: u64 = 70022;
-const TEST_API_KEY: &'static str = "ea454eef-ef74-42da-a2ed-b971bb212718";
-const TEST_READ_API_KEY: &'static str = "fd1baeda-e66f-4ebe-aa27-c791ae87ba86";
+/// Starts a mock server with the test account and creates a client connected to it.
+#[cfg(feature = "mock")]
+fn mock() -> (MockServer, Client) {
+    let server = MockServer::start().unwrap();
+    server.add_account(TEST_API_ID, TEST_API_KEY, TEST_READ_API_KEY);
+    let client = server.client().unwrap();
+    (server, client)
+}
 
+#[cfg(feature = "mock")]
 #[test]
 fn it_version_number() {
-    Client::new().unwrap();
+    let (_server, client) = mock();
+    assert_eq!("1.2.6", format!("{}", client.get_api_version().unwrap()));
+}
+
+#[cfg(feature = "mock")]
+#[test]
+fn it_lazy_client() {
+    let server = MockServer::start().unwrap();
+    let client = ClientBuilder::new().api_url(server.get_api_url()).build_lazy().unwrap();
+    assert!(client.get_cached_api_version().is_none());
+    assert_eq!("1.2.6", format!("{}", client.get_api_version().unwrap()));
+    assert_eq!("1.2.6", format!("{}", client.get_cached_api_version().unwrap()));
+
+    let offline = ClientBuilder::new().api_url("http://127.0.0.1:1/api").build_lazy().unwrap();
+    assert!(offline.get_api_version().is_err());
+    assert!(Client::new_lazy().unwrap().get_cached_api_version().is_none());
+}
+
+#[cfg(feature = "mock")]
+#[test]
+fn it_client_builder() {
+    let server = MockServer::start().unwrap();
+    let client = ClientBuilder::new().api_url(server.get_api_url()).build().unwrap();
+    assert_eq!(server.get_api_url(), client.get_api_url().as_str());
+}
+
+#[test]
+fn it_client_builder_invalid_url() {
+    assert!(ClientBuilder::new().api_url("not a valid url").build().is_err());
+}
+
+/// Transport that always answers with the same body, whatever the requested URL.
+#[derive(Debug)]
+struct StaticTransport {
+    body: &'static str,
+}
+
+impl Transport for StaticTransport {
+    fn get(&self, _url: Url) -> Result<Box<Read>> {
+        Ok(Box::new(Cursor::new(self.body)))
+    }
+}
+
+#[test]
+fn it_custom_transport() {
+    let transport =
+        StaticTransport { body: r#"{"result":{"api_version":"1.2.6"},"method":null}"# };
+    let client = ClientBuilder::new()
+        .api_url("http://example.com/api")
+        .transport(transport)
+        .build()
+        .unwrap();
+    assert_eq!("1.2.6", format!("{}", client.get_api_version().unwrap()));
+    assert!(client.get_orders(Location::Europe, Algorithm::X11).is_err());
+}
+
+#[test]
+fn it_response_envelope() {
+    let client = |body| {
+        ClientBuilder::new()
+            .api_url("http://example.com/api")
+            .transport(StaticTransport { body: body })
+            .build_lazy()
+            .unwrap()
+    };
+
+    let error = client(r#"{"result":{"error":"Incorrect key."}}"#)
+        .get_balance(TEST_API_ID, TEST_API_KEY)
+        .err()
+        .unwrap();
+    assert_eq!("Incorrect key.", error.description());
+    assert!(client(r#"{"result":{}}"#).get_orders(Location::USA, Algorithm::X11).is_err());
+    assert!(client(r#"{"result":{"orders":[],"other":1}}"#)
+        .get_orders(Location::USA, Algorithm::X11)
+        .is_err());
+    assert!(client(r#"{"result":{"orders":[]}}"#)
+        .get_orders(Location::USA, Algorithm::X11)
+        .unwrap()
+        .is_empty());
+    assert!(client(r#"{"result":{"success":"Order removed."}}"#)
+        .remove_order(TEST_API_ID, TEST_API_KEY, Location::USA, Algorithm::X11, 1)
+        .is_ok());
+}
+
+#[test]
+fn it_success_messages() {
+    let client = |body| {
+        ClientBuilder::new()
+            .api_url("http://example.com/api")
+            .transport(StaticTransport { body: body })
+            .build_lazy()
+            .unwrap()
+    };
+    let create_order = |body| {
+        client(body).create_order(TEST_API_ID,
+                                  TEST_API_KEY,
+                                  Location::USA,
+                                  NewOrder {
+                                      algorithm: Algorithm::X11,
+                                      amount: btc("0.5"),
+                                      price: btc("0.05"),
+                                      limit: None,
+                                  },
+                                  test_pool(),
+                                  None)
+    };
+    let decrease_order_price = |body| {
+        client(body).decrease_order_price(TEST_API_ID,
+                                          TEST_API_KEY,
+                                          Location::USA,
+                                          Algorithm::X11,
+                                          1)
+    };
+
+    assert_eq!(42,
+               create_order(r#"{"result":{"success":"Order 42 created."}}"#).unwrap());
+    for body in &[r#"{"result":{"success":"Ok"}}"#,
+                  r#"{"result":{"success":"Order created."}}"#,
+                  r#"{"result":{"success":"Orden 42 creada."}}"#] {
+        match create_order(body) {
+            Err(nicehash::error::Error::Api(_)) => {}
+            r => panic!("unexpected result: {:?}", r),
+        }
+    }
+
+    assert_eq!(btc("0.059"),
+               decrease_order_price(r#"{"result":{"success":"New order price set to: 0.0590"}}"#)
+                   .unwrap());
+    for body in &[r#"{"result":{"success":"Ok"}}"#,
+                  r#"{"result":{"success":"New order price set to: lower"}}"#] {
+        match decrease_order_price(body) {
+            Err(nicehash::error::Error::Api(_)) => {}
+            r => panic!("unexpected result: {:?}", r),
+        }
+    }
+}
+
+#[test]
+fn it_provider_history_time_out_of_range() {
+    let client = ClientBuilder::new()
+        .api_url("http://example.com/api")
+        .transport(StaticTransport {
+            body: r#"{"result":{"current":[],"payments":[],
+                      "past":[{"data":[[18446744073709551615,{},"0"]],"algo":3}]}}"#,
+        })
+        .build_lazy()
+        .unwrap();
+    match client.provider_stats_ex(TEST_BTC_ADDRESS, None) {
+        Err(nicehash::error::Error::Api(_)) => {}
+        r => panic!("unexpected result: {:?}", r),
+    }
+}
+
+#[test]
+fn it_provider_worker_connected_time_out_of_range() {
+    let client = ClientBuilder::new()
+        .api_url("http://example.com/api")
+        .transport(StaticTransport {
+            body: r#"{"result":{"workers":[["rig1",{},18446744073709551615,0,"0.5",0,3]],
+                      "algo":-1}}"#,
+        })
+        .build_lazy()
+        .unwrap();
+    match client.provider_workers(TEST_BTC_ADDRESS, None) {
+        Err(nicehash::error::Error::Api(_)) => {}
+        r => panic!("unexpected result: {:?}", r),
+    }
+}
+
+/// Transport that fails with a connection error in the first requests, and answers with the given
+/// body to the rest of them.
+#[derive(Debug)]
+struct FlakyTransport {
+    failures: usize,
+    requests: Arc<AtomicUsize>,
+    body: &'static str,
+}
+
+impl Transport for FlakyTransport {
+    fn get(&self, _url: Url) -> Result<Box<Read>> {
+        if self.requests.fetch_add(1, Ordering::SeqCst) < self.failures {
+            Err(hyper::Error::Io(io::Error::new(io::ErrorKind::ConnectionReset,
+                                                "connection reset"))
+                .into())
+        } else {
+            Ok(Box::new(Cursor::new(self.body)))
+        }
+    }
+}
+
+#[test]
+fn it_retry_policy() {
+    let client = |requests: &Arc<AtomicUsize>, body| {
+        ClientBuilder::new()
+            .api_url("http://example.com/api")
+            .transport(FlakyTransport {
+                failures: 2,
+                requests: requests.clone(),
+                body: body,
+            })
+            .retry_policy(RetryPolicy::new(3).initial_backoff(Duration::from_millis(1)))
+            .build_lazy()
+            .unwrap()
+    };
+
+    let requests = Arc::new(AtomicUsize::new(0));
+    assert!(client(&requests, r#"{"result":{"orders":[]}}"#)
+        .get_orders(Location::USA, Algorithm::X11)
+        .unwrap()
+        .is_empty());
+    assert_eq!(3, requests.load(Ordering::SeqCst));
+
+    let requests = Arc::new(AtomicUsize::new(0));
+    assert!(client(&requests, r#"{"result":{"success":"Order removed."}}"#)
+        .remove_order(TEST_API_ID, TEST_API_KEY, Location::USA, Algorithm::X11, 1)
+        .is_err());
+    assert_eq!(1, requests.load(Ordering::SeqCst));
+
+    let requests = Arc::new(AtomicUsize::new(0));
+    assert!(client(&requests, r#"{"result":{"error":"Incorrect key."}}"#)
+        .get_balance(TEST_API_ID, TEST_API_KEY)
+        .is_err());
+    assert_eq!(3, requests.load(Ordering::SeqCst));
+
+    // Errors reported by the API are not retried.
+    let requests = Arc::new(AtomicUsize::new(0));
+    let client = ClientBuilder::new()
+        .api_url("http://example.com/api")
+        .transport(FlakyTransport {
+            failures: 0,
+            requests: requests.clone(),
+            body: r#"{"result":{"error":"Something went wrong."}}"#,
+        })
+        .retry_policy(RetryPolicy::new(3).initial_backoff(Duration::from_millis(1)))
+        .build_lazy()
+        .unwrap();
+    assert!(client.get_orders(Location::USA, Algorithm::X11).is_err());
+    assert_eq!(1, requests.load(Ordering::SeqCst));
 }
 
+#[test]
+fn it_rate_limiter() {
+    // Requests are limited by default.
+    let client = ClientBuilder::new().build_lazy().unwrap();
+    assert_eq!(Some(RateLimit::new(60, Duration::from_secs(60))),
+               client.get_rate_limiter().get_public_limit());
+    assert_eq!(Some(RateLimit::new(30, Duration::from_secs(60))),
+               client.get_rate_limiter().get_private_limit());
+
+    // The limits can be disabled.
+    let client = ClientBuilder::new().rate_limiter(RateLimiter::unlimited()).build_lazy().unwrap();
+    assert_eq!(None, client.get_rate_limiter().get_public_limit());
+    assert_eq!(None, client.get_rate_limiter().get_private_limit());
+
+    let limiter = RateLimiter::new(RateLimit::new(2, Duration::from_millis(200)),
+                                   RateLimit::new(2, Duration::from_secs(60)));
+    let client = ClientBuilder::new()
+        .api_url("http://example.com/api")
+        .transport(StaticTransport { body: r#"{"result":{"orders":[]}}"# })
+        .rate_limiter(limiter.clone())
+        .build_lazy()
+        .unwrap();
+    assert_eq!(Some(RateLimit::new(2, Duration::from_secs(60))),
+               client.get_rate_limiter().get_private_limit());
+
+    // Private methods have their own budget.
+    let start = Instant::now();
+    for _ in 0..2 {
+        let _ = client.get_my_orders(TEST_API_ID, TEST_API_KEY, Location::USA, Algorithm::X11)
+            .unwrap();
+    }
+    assert!(start.elapsed() < Duration::from_millis(100));
+
+    // Clones of the client share the public budget.
+    let clone = client.clone();
+    let start = Instant::now();
+    for _ in 0..2 {
+        let _ = client.get_orders(Location::USA, Algorithm::X11).unwrap();
+        let _ = clone.get_orders(Location::USA, Algorithm::X11).unwrap();
+    }
+    assert!(start.elapsed() >= Duration::from_millis(150));
+}
+
+/// Transport that waits for the given delay before answering, like a socket with a read timeout.
+#[derive(Debug)]
+struct SlowTransport {
+    delay: Duration,
+}
+
+impl Transport for SlowTransport {
+    fn get(&self, url: Url) -> Result<Box<Read>> {
+        self.get_with_timeout(url, None)
+    }
+
+    fn get_with_timeout(&self, _url: Url, timeout: Option<Duration>) -> Result<Box<Read>> {
+        match timeout {
+            Some(timeout) if timeout < self.delay => {
+                thread::sleep(timeout);
+                let error = io::Error::new(io::ErrorKind::TimedOut, "read timed out");
+                Err(nicehash::error::Error::Hyper(hyper::Error::Io(error)))
+            }
+            _ => {
+                thread::sleep(self.delay);
+                Ok(Box::new(Cursor::new(r#"{"result":{"orders":[]}}"#)))
+            }
+        }
+    }
+}
+
+#[test]
+fn it_timeout() {
+    let client = ClientBuilder::new()
+        .api_url("http://example.com/api")
+        .transport(SlowTransport { delay: Duration::from_millis(200) })
+        .timeout(Duration::from_millis(20))
+        .build_lazy()
+        .unwrap();
+    assert_eq!(Some(Duration::from_millis(20)), client.get_timeout());
+
+    match client.get_orders(Location::USA, Algorithm::X11) {
+        Err(nicehash::error::Error::Timeout(_)) => {}
+        r => panic!("unexpected result: {:?}", r),
+    }
+    assert!(client.with_timeout(Some(Duration::from_secs(5)))
+        .get_orders(Location::USA, Algorithm::X11)
+        .is_ok());
+    assert!(client.with_timeout(None).get_orders(Location::USA, Algorithm::X11).is_ok());
+    assert_eq!(Some(Duration::from_millis(20)), client.get_timeout());
+}
+
+#[test]
+fn it_timeout_socket() {
+    // The server accepts the connection but never answers.
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let _ = thread::spawn(move || {
+        let mut connections = Vec::new();
+        for connection in listener.incoming() {
+            connections.push(connection);
+        }
+    });
+
+    let client = ClientBuilder::new()
+        .api_url(format!("http://{}/api", address))
+        .timeout(Duration::from_millis(200))
+        .build_lazy()
+        .unwrap();
+    let start = Instant::now();
+    match client.get_orders(Location::USA, Algorithm::X11) {
+        Err(nicehash::error::Error::Timeout(_)) => {}
+        r => panic!("unexpected result: {:?}", r),
+    }
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[cfg(feature = "mock")]
 #[test]
 fn it_global_stats_current() {
-    let client = Client::new().unwrap();
+    let (_server, client) = mock();
 
     client.global_stats_current(None).unwrap();
     client.global_stats_current(Some(Location::Europe)).unwrap();
     client.global_stats_current(Some(Location::USA)).unwrap();
 }
 
+#[cfg(feature = "mock")]
 #[test]
 fn it_global_stats_24h() {
-    let client = Client::new().unwrap();
+    let (_server, client) = mock();
     client.global_stats_24h().unwrap();
 }
 
+#[cfg(feature = "mock")]
 #[test]
 fn it_get_orders() {
-    let client = Client::new().unwrap();
+    let (_server, client) = mock();
     client.get_orders(Location::Europe, Algorithm::SHA256).unwrap();
     client.get_orders(Location::USA, Algorithm::DaggerHashimoto).unwrap();
     client.get_orders(Location::Europe, Algorithm::Equihash).unwrap();
     client.get_orders(Location::USA, Algorithm::X15).unwrap();
 }
 
+#[cfg(feature = "mock")]
 #[test]
 fn it_get_buy_info() {
-    let client = Client::new().unwrap();
+    let (_server, client) = mock();
     let info = client.get_buy_info().unwrap();
     assert_eq!(info.get_down_time(), Duration::from_secs(600));
 }
 
+#[cfg(feature = "mock")]
+#[test]
+fn it_multialgo_info() {
+    let (server, client) = mock();
+    let mut order = MockOrder::new(Location::Europe, Algorithm::Lbry, btc("0.0042"));
+    order.accepted_speed = 10.0;
+    let _ = server.add_order(order);
+
+    let info = client.multialgo_info().unwrap();
+    assert_eq!(25, info.get_all_info().len());
+    let lbry = info.get_info_for(Algorithm::Lbry).unwrap();
+    assert_eq!("lbry", lbry.get_name());
+    assert_eq!(3356, lbry.get_port());
+    assert_eq!(0.0042, lbry.get_paying());
+
+    let info = client.simple_multialgo_info().unwrap();
+    assert_eq!(Algorithm::Scrypt, info.get_all_info()[0].get_algorithm());
+    assert_eq!(0.0, info.get_info_for(Algorithm::SHA256).unwrap().get_paying());
+}
+
+#[cfg(feature = "mock")]
+#[test]
+fn it_provider_stats() {
+    let (server, client) = mock();
+    server.set_provider_stat(TEST_BTC_ADDRESS,
+                             MockProviderStat {
+                                 algorithm: Algorithm::Equihash,
+                                 balance: btc("0.00012"),
+                                 accepted_speed: 250.0,
+                                 rejected_speed: 3.5,
+                                 profitability: 0.0000012,
+                             });
+
+    let stats = client.provider_stats(TEST_BTC_ADDRESS).unwrap();
+    assert_eq!(1, stats.get_all_stats().len());
+    assert!(stats.get_stats_for(Algorithm::SHA256).is_none());
+    let stat = stats.get_stats_for(Algorithm::Equihash).unwrap();
+    assert_eq!(btc("0.00012"), stat.get_balance());
+    assert!((stat.get_accepted_speed() - 250.0).abs() < f64::EPSILON);
+    assert!((stat.get_rejected_speed() - 3.5).abs() < f64::EPSILON);
+
+    assert!(client.provider_stats("").is_err());
+}
+
+#[cfg(feature = "mock")]
+#[test]
+fn it_provider_stats_ex() {
+    let (server, client) = mock();
+    server.set_provider_stat(TEST_BTC_ADDRESS,
+                             MockProviderStat {
+                                 algorithm: Algorithm::X11,
+                                 balance: btc("0.0005"),
+                                 accepted_speed: 12.5,
+                                 rejected_speed: 0.25,
+                                 profitability: 0.0003,
+                             });
+
+    let stats = client.provider_stats_ex(TEST_BTC_ADDRESS, None).unwrap();
+    assert_eq!(Some(TEST_BTC_ADDRESS), stats.get_addr());
+    assert!(stats.get_payments().is_empty());
+    let current = stats.get_current_for(Algorithm::X11).unwrap();
+    assert_eq!("MH", current.get_speed_text());
+    assert!((current.get_profitability() - 0.0003).abs() < f64::EPSILON);
+    assert!((current.get_accepted_speed() - 12.5).abs() < f64::EPSILON);
+    assert!((current.get_rejected_speed() - 0.25).abs() < f64::EPSILON);
+    assert_eq!(btc("0.0005"), current.get_balance());
+
+    let history = stats.get_history_for(Algorithm::X11).unwrap();
+    assert_eq!(1, history.get_samples().len());
+    let sample = &history.get_samples()[0];
+    assert!(sample.get_time() <= SystemTime::now());
+    assert!((sample.get_accepted_speed() - 12.5).abs() < f64::EPSILON);
+
+    let future = SystemTime::now() + Duration::from_secs(3600);
+    let stats = client.provider_stats_ex(TEST_BTC_ADDRESS, Some(future)).unwrap();
+    assert!(stats.get_history().is_empty());
+}
+
+#[cfg(feature = "mock")]
+#[test]
+fn it_provider_payments() {
+    let (server, client) = mock();
+    assert!(client.provider_payments(TEST_BTC_ADDRESS).unwrap().is_empty());
+
+    let time = UNIX_EPOCH + Duration::from_secs(1483315200);
+    server.add_provider_payment(TEST_BTC_ADDRESS,
+                                MockPayment {
+                                    amount: btc("0.0125"),
+                                    fee: btc("0.0005"),
+                                    transaction_id: "f1a9e3b0".to_owned(),
+                                    time: time,
+                                });
+    let payments = client.provider_payments(TEST_BTC_ADDRESS).unwrap();
+    assert_eq!(1, payments.len());
+    assert_eq!(btc("0.0125"), payments[0].get_amount());
+    assert_eq!(btc("0.0005"), payments[0].get_fee());
+    assert_eq!("f1a9e3b0", payments[0].get_transaction_id());
+    assert_eq!(time, payments[0].get_time());
+}
+
+#[cfg(feature = "mock")]
+#[test]
+fn it_provider_workers() {
+    let (server, client) = mock();
+    server.add_provider_worker(TEST_BTC_ADDRESS,
+                               MockWorker {
+                                   name: "rig1".to_owned(),
+                                   algorithm: Algorithm::DaggerHashimoto,
+                                   accepted_speed: 85.3,
+                                   rejected_speed: 0.4,
+                                   connected_time: Duration::from_secs(3600),
+                                   difficulty: 0.5,
+                                   location: Location::USA,
+                               });
+    server.add_provider_worker(TEST_BTC_ADDRESS,
+                               MockWorker {
+                                   name: "rig2".to_owned(),
+                                   algorithm: Algorithm::Equihash,
+                                   accepted_speed: 0.0,
+                                   rejected_speed: 0.0,
+                                   connected_time: Duration::from_secs(120),
+                                   difficulty: 16.0,
+                                   location: Location::Europe,
+                               });
+
+    let workers = client.provider_workers(TEST_BTC_ADDRESS, None).unwrap();
+    assert_eq!(2, workers.len());
+    assert_eq!("rig1", workers[0].get_name());
+    assert_eq!(Algorithm::DaggerHashimoto, workers[0].get_algorithm());
+    assert_eq!(Location::USA, workers[0].get_location());
+    assert_eq!(Duration::from_secs(3600), workers[0].get_connected_time());
+    assert!((workers[0].get_accepted_speed() - 85.3).abs() < f64::EPSILON);
+    assert!((workers[0].get_rejected_speed() - 0.4).abs() < f64::EPSILON);
+    assert!((workers[0].get_difficulty() - 0.5).abs() < f64::EPSILON);
+
+    let workers = client.provider_workers(TEST_BTC_ADDRESS, Some(Algorithm::Equihash)).unwrap();
+    assert_eq!(1, workers.len());
+    assert_eq!("rig2", workers[0].get_name());
+    assert_eq!(Algorithm::Equihash, workers[0].get_algorithm());
+    assert_eq!(Location::Europe, workers[0].get_location());
+}
+
+#[cfg(feature = "mock")]
 #[test]
 fn it_get_my_orders() {
-    let client = Client::new().unwrap();
+    let (_server, client) = mock();
     client.get_my_orders(TEST_API_ID,
                        TEST_API_KEY,
                        Location::Europe,
@@ -68,9 +599,10 @@ fn it_get_my_orders() {
     client.get_my_orders(TEST_API_ID, TEST_API_KEY, Location::USA, Algorithm::X15).unwrap();
 }
 
+#[cfg(feature = "mock")]
 #[test]
 fn it_get_my_orders_errors() {
-    let client = Client::new().unwrap();
+    let (_server, client) = mock();
     assert_eq!("Incorrect key.",
                client.get_my_orders(TEST_API_ID,
                                   "invalid-api-key",
@@ -105,25 +637,95 @@ fn it_get_my_orders_errors() {
                    .description());
 }
 
+#[test]
+fn it_result_error_from_message() {
+    use nicehash::error::Error;
+
+    match Error::from_result("Incorrect key.") {
+        Error::InvalidKey(ref message) => assert_eq!("Incorrect key.", message),
+        e => panic!("unexpected error: {:?}", e),
+    }
+    match Error::from_result("Order id incorrect.") {
+        Error::OrderNotFound(_) => {}
+        e => panic!("unexpected error: {:?}", e),
+    }
+    for message in &["incorrect key", "Invalid key.", "Order id/limit/algo incorrect.",
+                     "Invalid limit."] {
+        match Error::from_result(*message) {
+            Error::Result(ref m) => assert_eq!(message, m),
+            e => panic!("unexpected error: {:?}", e),
+        }
+    }
+}
+
+#[cfg(feature = "mock")]
+#[test]
+fn it_result_error_kinds() {
+    let (server, client) = mock();
+    match client.get_balance(TEST_API_ID, "invalid-api-key") {
+        Err(nicehash::error::Error::InvalidKey(ref message)) => {
+            assert_eq!("Incorrect key.", message)
+        }
+        r => panic!("unexpected result: {:?}", r),
+    }
+    match client.remove_order(TEST_API_ID, TEST_API_KEY, Location::USA, Algorithm::X11, 1) {
+        Err(nicehash::error::Error::OrderNotFound(_)) => {}
+        r => panic!("unexpected result: {:?}", r),
+    }
+
+    let order = NewOrder {
+        algorithm: Algorithm::X11,
+        amount: btc("0.5"),
+        price: btc("0.05"),
+        limit: None,
+    };
+    match client.create_order(TEST_API_ID, TEST_API_KEY, Location::USA, order, test_pool(), None) {
+        Err(nicehash::error::Error::InsufficientBalance(_)) => {}
+        r => panic!("unexpected result: {:?}", r),
+    }
+
+    server.set_balance(TEST_API_ID, btc("1.0"), btc("0.0"));
+    let order = NewOrder {
+        algorithm: Algorithm::X11,
+        amount: btc("0.5"),
+        price: btc("0.05"),
+        limit: None,
+    };
+    let id = client.create_order(TEST_API_ID, TEST_API_KEY, Location::USA, order, test_pool(), None)
+        .unwrap();
+    let _ = client.decrease_order_price(TEST_API_ID,
+                              TEST_API_KEY,
+                              Location::USA,
+                              Algorithm::X11,
+                              id)
+        .unwrap();
+    match client.decrease_order_price(TEST_API_ID,
+                                      TEST_API_KEY,
+                                      Location::USA,
+                                      Algorithm::X11,
+                                      id) {
+        Err(nicehash::error::Error::PriceDecreaseTooSoon(ref message)) => {
+            assert_eq!("Price decrease too soon.", message)
+        }
+        r => panic!("unexpected result: {:?}", r),
+    }
+}
+
+#[cfg(feature = "mock")]
 #[test]
 fn it_create_order_errors() {
-    let client = Client::new().unwrap();
+    let (_server, client) = mock();
     assert_eq!("Not enough funds.",
                client.create_order(TEST_API_ID,
                                  TEST_API_KEY,
                                  Location::USA,
                                  NewOrder {
                                      algorithm: Algorithm::Equihash,
-                                     amount: 0.34,
-                                     price: 0.0985,
-                                     limit: Some(1.5),
-                                 },
-                                 PoolInfo {
-                                     host: "my.test.pool".to_owned(),
-                                     port: 5650,
-                                     username: "TestUser".to_owned(),
-                                     password: "test_password".to_owned(),
+                                     amount: btc("0.34"),
+                                     price: btc("0.0985"),
+                                     limit: Some(Hashrate::new(1.5, HashrateUnit::Solution)),
                                  },
+                                 test_pool(),
                                  None)
                    .err()
                    .unwrap()
@@ -134,16 +736,11 @@ fn it_create_order_errors() {
                                  Location::USA,
                                  NewOrder {
                                      algorithm: Algorithm::X15,
-                                     amount: 0.34,
-                                     price: 0.0985,
-                                     limit: Some(1.5),
-                                 },
-                                 PoolInfo {
-                                     host: "my.test.pool".to_owned(),
-                                     port: 5650,
-                                     username: "TestUser".to_owned(),
-                                     password: "test_password".to_owned(),
+                                     amount: btc("0.34"),
+                                     price: btc("0.0985"),
+                                     limit: Some(Hashrate::new(1.5, HashrateUnit::MegaHash)),
                                  },
+                                 test_pool(),
                                  None)
                    .err()
                    .unwrap()
@@ -154,16 +751,11 @@ fn it_create_order_errors() {
                                  Location::USA,
                                  NewOrder {
                                      algorithm: Algorithm::SHA256,
-                                     amount: 0.0,
-                                     price: 0.0985,
+                                     amount: btc("0"),
+                                     price: btc("0.0985"),
                                      limit: None,
                                  },
-                                 PoolInfo {
-                                     host: "my.test.pool".to_owned(),
-                                     port: 5650,
-                                     username: "TestUser".to_owned(),
-                                     password: "test_password".to_owned(),
-                                 },
+                                 test_pool(),
                                  None)
                    .err()
                    .unwrap()
@@ -174,16 +766,11 @@ fn it_create_order_errors() {
                                  Location::USA,
                                  NewOrder {
                                      algorithm: Algorithm::SHA256,
-                                     amount: 0.15,
-                                     price: 0.0,
+                                     amount: btc("0.15"),
+                                     price: btc("0"),
                                      limit: None,
                                  },
-                                 PoolInfo {
-                                     host: "my.test.pool".to_owned(),
-                                     port: 5650,
-                                     username: "TestUser".to_owned(),
-                                     password: "test_password".to_owned(),
-                                 },
+                                 test_pool(),
                                  None)
                    .err()
                    .unwrap()
@@ -194,16 +781,11 @@ fn it_create_order_errors() {
                                  Location::USA,
                                  NewOrder {
                                      algorithm: Algorithm::SHA256,
-                                     amount: 0.15,
-                                     price: 0.095,
+                                     amount: btc("0.15"),
+                                     price: btc("0.095"),
                                      limit: None,
                                  },
-                                 PoolInfo {
-                                     host: "my.test.pool".to_owned(),
-                                     port: 5650,
-                                     username: "TestUser".to_owned(),
-                                     password: "test_password".to_owned(),
-                                 },
+                                 test_pool(),
                                  Some(127))
                    .err()
                    .unwrap()
@@ -214,16 +796,11 @@ fn it_create_order_errors() {
                                  Location::USA,
                                  NewOrder {
                                      algorithm: Algorithm::SHA256,
-                                     amount: 0.0,
-                                     price: 0.095,
+                                     amount: btc("0"),
+                                     price: btc("0.095"),
                                      limit: None,
                                  },
-                                 PoolInfo {
-                                     host: "my.test.pool".to_owned(),
-                                     port: 5650,
-                                     username: "TestUser".to_owned(),
-                                     password: "test_password".to_owned(),
-                                 },
+                                 test_pool(),
                                  Some(127))
                    .err()
                    .unwrap()
@@ -234,32 +811,28 @@ fn it_create_order_errors() {
                                  Location::USA,
                                  NewOrder {
                                      algorithm: Algorithm::SHA256,
-                                     amount: 0.15,
-                                     price: 0.0,
+                                     amount: btc("0.15"),
+                                     price: btc("0"),
                                      limit: None,
                                  },
-                                 PoolInfo {
-                                     host: "my.test.pool".to_owned(),
-                                     port: 5650,
-                                     username: "TestUser".to_owned(),
-                                     password: "test_password".to_owned(),
-                                 },
+                                 test_pool(),
                                  Some(127))
                    .err()
                    .unwrap()
                    .description());
 }
 
+#[cfg(feature = "mock")]
 #[test]
 fn it_refill_order_errors() {
-    let client = Client::new().unwrap();
+    let (_server, client) = mock();
     assert_eq!("Not enough funds.",
                client.refill_order(TEST_API_ID,
                                  TEST_API_KEY,
                                  Location::Europe,
                                  Algorithm::Equihash,
                                  10,
-                                 5.0)
+                                 btc("5"))
                    .err()
                    .unwrap()
                    .description());
@@ -269,7 +842,7 @@ fn it_refill_order_errors() {
                                  Location::Europe,
                                  Algorithm::Equihash,
                                  0,
-                                 5.0)
+                                 btc("5"))
                    .err()
                    .unwrap()
                    .description());
@@ -279,7 +852,7 @@ fn it_refill_order_errors() {
                                  Location::USA,
                                  Algorithm::Axiom,
                                  10,
-                                 0.0)
+                                 btc("0"))
                    .err()
                    .unwrap()
                    .description());
@@ -289,7 +862,7 @@ fn it_refill_order_errors() {
                                  Location::Europe,
                                  Algorithm::Qubit,
                                  10,
-                                 5.0)
+                                 btc("5"))
                    .err()
                    .unwrap()
                    .description());
@@ -299,15 +872,16 @@ fn it_refill_order_errors() {
                                  Location::USA,
                                  Algorithm::Quark,
                                  10,
-                                 5.0)
+                                 btc("5"))
                    .err()
                    .unwrap()
                    .description());
 }
 
+#[cfg(feature = "mock")]
 #[test]
 fn it_remove_order_errors() {
-    let client = Client::new().unwrap();
+    let (_server, client) = mock();
     assert_eq!("Unknown order id.",
                client.remove_order(TEST_API_ID,
                                  TEST_API_KEY,
@@ -337,16 +911,17 @@ fn it_remove_order_errors() {
                    .description());
 }
 
+#[cfg(feature = "mock")]
 #[test]
 fn it_set_order_price_errors() {
-    let client = Client::new().unwrap();
+    let (_server, client) = mock();
     assert_eq!("Order id/price/algo incorrect.",
                client.set_order_price(TEST_API_ID,
                                     TEST_API_KEY,
                                     Location::Europe,
                                     Algorithm::Equihash,
                                     0,
-                                    10.3)
+                                    btc("10.3"))
                    .err()
                    .unwrap()
                    .description());
@@ -356,7 +931,7 @@ fn it_set_order_price_errors() {
                                     Location::Europe,
                                     Algorithm::Equihash,
                                     10,
-                                    0.0)
+                                    btc("0"))
                    .err()
                    .unwrap()
                    .description());
@@ -366,7 +941,7 @@ fn it_set_order_price_errors() {
                                     Location::Europe,
                                     Algorithm::Equihash,
                                     0,
-                                    0.0)
+                                    btc("0"))
                    .err()
                    .unwrap()
                    .description());
@@ -376,7 +951,7 @@ fn it_set_order_price_errors() {
                                     Location::Europe,
                                     Algorithm::Equihash,
                                     10,
-                                    0.5)
+                                    btc("0.5"))
                    .err()
                    .unwrap()
                    .description());
@@ -386,7 +961,7 @@ fn it_set_order_price_errors() {
                                     Location::Europe,
                                     Algorithm::Qubit,
                                     10,
-                                    5.5)
+                                    btc("5.5"))
                    .err()
                    .unwrap()
                    .description());
@@ -396,15 +971,16 @@ fn it_set_order_price_errors() {
                                     Location::USA,
                                     Algorithm::Quark,
                                     10,
-                                    4.3)
+                                    btc("4.3"))
                    .err()
                    .unwrap()
                    .description());
 }
 
+#[cfg(feature = "mock")]
 #[test]
 fn it_decrease_order_price_errors() {
-    let client = Client::new().unwrap();
+    let (_server, client) = mock();
     assert_eq!("Order id/price/algo incorrect.",
                client.decrease_order_price(TEST_API_ID,
                                          TEST_API_KEY,
@@ -443,9 +1019,10 @@ fn it_decrease_order_price_errors() {
                    .description());
 }
 
+#[cfg(feature = "mock")]
 #[test]
 fn it_set_order_speed_limit_errors() {
-    let client = Client::new().unwrap();
+    let (_server, client) = mock();
     assert_eq!("Order id/limit/algo incorrect.",
                client.set_order_speed_limit(TEST_API_ID,
                                           TEST_API_KEY,
@@ -462,7 +1039,7 @@ fn it_set_order_speed_limit_errors() {
                                           Location::Europe,
                                           Algorithm::Equihash,
                                           10,
-                                          Some(-5.4))
+                                          Some(Hashrate::new(0.0, HashrateUnit::Solution)))
                    .err()
                    .unwrap()
                    .description());
@@ -472,7 +1049,7 @@ fn it_set_order_speed_limit_errors() {
                                           Location::Europe,
                                           Algorithm::Equihash,
                                           10,
-                                          Some(1.1))
+                                          Some(Hashrate::new(1.1, HashrateUnit::Solution)))
                    .err()
                    .unwrap()
                    .description());
@@ -482,7 +1059,7 @@ fn it_set_order_speed_limit_errors() {
                                           Location::Europe,
                                           Algorithm::Qubit,
                                           10,
-                                          Some(1.5))
+                                          Some(Hashrate::new(1.5, HashrateUnit::MegaHash)))
                    .err()
                    .unwrap()
                    .description());
@@ -498,12 +1075,13 @@ fn it_set_order_speed_limit_errors() {
                    .description());
 }
 
+#[cfg(feature = "mock")]
 #[test]
 fn it_get_balance() {
-    let client = Client::new().unwrap();
+    let (_server, client) = mock();
     let balance = client.get_balance(TEST_API_ID, TEST_API_KEY).unwrap();
-    assert!(balance.confirmed < f64::EPSILON && balance.confirmed > f64::EPSILON * -1.0);
-    assert!(balance.pending < f64::EPSILON && balance.pending > f64::EPSILON * -1.0);
+    assert!(balance.confirmed.is_zero());
+    assert!(balance.pending.is_zero());
 
     assert_eq!("Incorrect key.",
                client.get_balance(99999999, TEST_API_KEY)
@@ -516,3 +1094,167 @@ fn it_get_balance() {
                    .unwrap()
                    .description());
 }
+
+#[cfg(feature = "mock")]
+#[test]
+fn it_mock_order_book() {
+    let (server, client) = mock();
+    let mut order = MockOrder::new(Location::Europe, Algorithm::SHA256, btc("0.0123"));
+    order.accepted_speed = 1.5;
+    order.workers = 3;
+    let id = server.add_order(order);
+    let _ = server.add_order(MockOrder::new(Location::USA, Algorithm::SHA256, btc("0.0150")));
+
+    let orders = client.get_orders(Location::Europe, Algorithm::SHA256).unwrap();
+    assert_eq!(1, orders.len());
+    assert_eq!(id, orders[0].get_id());
+    assert_eq!(3, orders[0].get_workers());
+    assert_eq!(btc("0.0123"), orders[0].get_price());
+
+    let stats = client.global_stats_current(Some(Location::Europe)).unwrap();
+    assert!((stats.get_stats_for(Algorithm::SHA256).get_speed() - 1.5).abs() < f64::EPSILON);
+    assert!(client.get_my_orders(TEST_API_ID, TEST_API_KEY, Location::Europe, Algorithm::SHA256)
+        .unwrap()
+        .is_empty());
+}
+
+#[cfg(feature = "mock")]
+#[test]
+fn it_mock_order_lifecycle() {
+    let (server, client) = mock();
+    server.set_balance(TEST_API_ID, btc("1.0"), btc("0.0"));
+    let order = NewOrder {
+        algorithm: Algorithm::X11,
+        amount: btc("0.5"),
+        price: btc("0.05"),
+        limit: Some(Hashrate::new(2.0, HashrateUnit::MegaHash)),
+    };
+    let id = client.create_order(TEST_API_ID,
+                      TEST_API_KEY,
+                      Location::Europe,
+                      order,
+                      test_pool(),
+                      None)
+        .unwrap();
+    assert_eq!(Some(test_pool()), server.get_order_pool(id));
+    assert_eq!(2.0, server.get_order(id).unwrap().limit_speed);
+
+    let orders = client.get_my_orders(TEST_API_ID,
+                       TEST_READ_API_KEY,
+                       Location::Europe,
+                       Algorithm::X11)
+        .unwrap();
+    assert_eq!(1, orders.len());
+    assert_eq!(id, orders[0].get_id());
+
+    client.refill_order(TEST_API_ID,
+                      TEST_API_KEY,
+                      Location::Europe,
+                      Algorithm::X11,
+                      id,
+                      btc("0.1"))
+        .unwrap();
+    // The refill also pays the service fee.
+    let balance = client.get_balance(TEST_API_ID, TEST_READ_API_KEY).unwrap();
+    assert_eq!(btc("0.397"), balance.confirmed);
+
+    client.set_order_price(TEST_API_ID,
+                         TEST_API_KEY,
+                         Location::Europe,
+                         Algorithm::X11,
+                         id,
+                         btc("0.06"))
+        .unwrap();
+    let price = client.decrease_order_price(TEST_API_ID,
+                              TEST_API_KEY,
+                              Location::Europe,
+                              Algorithm::X11,
+                              id)
+        .unwrap();
+    assert_eq!(btc("0.059"), price);
+    assert_eq!("Price decrease too soon.",
+               client.decrease_order_price(TEST_API_ID,
+                                         TEST_API_KEY,
+                                         Location::Europe,
+                                         Algorithm::X11,
+                                         id)
+                   .err()
+                   .unwrap()
+                   .description());
+
+    client.set_order_speed_limit(TEST_API_ID,
+                               TEST_API_KEY,
+                               Location::Europe,
+                               Algorithm::X11,
+                               id,
+                               Some(Hashrate::new(500.0, HashrateUnit::KiloHash)))
+        .unwrap();
+    assert_eq!(0.5, server.get_order(id).unwrap().limit_speed);
+    client.set_order_speed_limit(TEST_API_ID,
+                               TEST_API_KEY,
+                               Location::Europe,
+                               Algorithm::X11,
+                               id,
+                               None)
+        .unwrap();
+    assert!(server.get_order(id).unwrap().limit_speed < f64::EPSILON);
+
+    client.remove_order(TEST_API_ID, TEST_API_KEY, Location::Europe, Algorithm::X11, id).unwrap();
+    assert!(server.get_order(id).is_none());
+    let balance = client.get_balance(TEST_API_ID, TEST_API_KEY).unwrap();
+    assert_eq!(btc("0.997"), balance.confirmed);
+}
+
+#[cfg(feature = "mock")]
+#[test]
+fn it_mock_read_only_key() {
+    let (server, client) = mock();
+    server.set_balance(TEST_API_ID, btc("1.0"), btc("0.0"));
+    assert_eq!("Read-only API key.",
+               client.create_order(TEST_API_ID,
+                                 TEST_READ_API_KEY,
+                                 Location::USA,
+                                 NewOrder {
+                                     algorithm: Algorithm::Equihash,
+                                     amount: btc("0.34"),
+                                     price: btc("0.0985"),
+                                     limit: None,
+                                 },
+                                 test_pool(),
+                                 None)
+                   .err()
+                   .unwrap()
+                   .description());
+}
+
+#[cfg(feature = "mock")]
+#[test]
+fn it_authenticated_client() {
+    let (server, client) = mock();
+    server.set_balance(TEST_API_ID, btc("1.0"), btc("0.0"));
+
+    let full = client.authenticated(Credentials::new(TEST_API_ID, TEST_API_KEY));
+    assert_eq!(KeyAccess::Full, full.get_credentials().get_access());
+    assert!(!format!("{:?}", full).contains(TEST_API_KEY));
+    let id = full.create_order(Location::Europe,
+                      NewOrder {
+                          algorithm: Algorithm::X11,
+                          amount: btc("0.5"),
+                          price: btc("0.05"),
+                          limit: None,
+                      },
+                      test_pool(),
+                      None)
+        .unwrap();
+    assert_eq!(1, full.get_my_orders(Location::Europe, Algorithm::X11).unwrap().len());
+
+    let read_only = client.authenticated(Credentials::read_only(TEST_API_ID, TEST_READ_API_KEY));
+    assert_eq!(btc("0.5"), read_only.get_balance().unwrap().confirmed);
+    assert_eq!(1,
+               read_only.get_my_orders(Location::Europe, Algorithm::X11).unwrap().len());
+    match read_only.remove_order(Location::Europe, Algorithm::X11, id) {
+        Err(nicehash::error::Error::ReadOnlyKey(_)) => {}
+        r => panic!("unexpected result: {:?}", r),
+    }
+    assert!(server.get_order(id).is_some());
+}
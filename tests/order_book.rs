@@ -0,0 +1,125 @@
+#![cfg(feature = "mock")]
+
+extern crate nicehash;
+
+mod common;
+
+use nicehash::{Location, Algorithm, OrderType};
+use nicehash::hashrate::{Hashrate, HashrateUnit};
+use nicehash::mock::{MockServer, MockOrder};
+
+use common::btc;
+
+/// Creates a hashrate in `MH/s`, the native unit of X11 in the mock server.
+fn mh(speed: f64) -> Hashrate {
+    Hashrate::new(speed, HashrateUnit::MegaHash)
+}
+
+/// Creates a mock order with the given price, accepted speed and workers.
+fn order(order_type: OrderType, price: &str, accepted_speed: f64, workers: u64) -> MockOrder {
+    let mut order = MockOrder::new(Location::Europe, Algorithm::X11, btc(price));
+    order.order_type = order_type;
+    order.accepted_speed = accepted_speed;
+    order.workers = workers;
+    order
+}
+
+#[test]
+fn it_order_book() {
+    let server = MockServer::start().unwrap();
+    let _ = server.add_order(order(OrderType::Standard, "0.04", 1.0, 2));
+    let _ = server.add_order(order(OrderType::Standard, "0.05", 2.0, 4));
+    let _ = server.add_order(order(OrderType::Standard, "0.03", 0.0, 0));
+    let _ = server.add_order(order(OrderType::Fixed, "0.06", 4.0, 5));
+    let _ = server.add_order(order(OrderType::Standard, "0.04", 1.0, 1));
+    let mut dead = order(OrderType::Standard, "0.02", 0.0, 0);
+    dead.alive = false;
+    let _ = server.add_order(dead);
+    let _ = server.add_order(MockOrder::new(Location::USA, Algorithm::X11, btc("0.1")));
+    let client = server.client().unwrap();
+
+    let book = client.get_order_book(Location::Europe, Algorithm::X11).unwrap();
+    assert_eq!(6, book.get_orders().len());
+    assert_eq!(btc("0.06"), book.get_orders()[0].get_price());
+    assert_eq!(btc("0.02"), book.get_orders()[5].get_price());
+    assert_eq!(8.0, book.get_total_speed());
+    assert_eq!(1, book.get_orders_by_type(OrderType::Fixed).len());
+    assert_eq!(5, book.get_orders_by_type(OrderType::Standard).len());
+
+    let levels = book.get_price_levels();
+    assert_eq!(vec![btc("0.06"), btc("0.05"), btc("0.04"), btc("0.03"), btc("0.02")],
+               levels.iter().map(|level| level.get_price()).collect::<Vec<_>>());
+    assert_eq!(vec![4.0, 6.0, 8.0, 8.0, 8.0],
+               levels.iter().map(|level| level.get_cumulative_speed()).collect::<Vec<_>>());
+    assert_eq!(2, levels[2].get_orders());
+    assert_eq!(3, levels[2].get_workers());
+    assert_eq!(2.0, levels[2].get_accepted_speed());
+
+    assert_eq!(Some(btc("0.04")), book.get_price_for_share(0.25));
+    assert_eq!(Some(btc("0.05")), book.get_price_for_share(0.5));
+    assert_eq!(Some(btc("0.06")), book.get_price_for_share(1.0));
+    assert_eq!(Some(btc("0.04")), book.get_lowest_working_price());
+
+    let standard = book.filter_by_type(OrderType::Standard);
+    assert_eq!(4.0, standard.get_total_speed());
+    assert_eq!(Some(btc("0.04")), standard.get_price_for_share(0.5));
+    // Fixed price orders get their hashrate regardless of their price.
+    assert_eq!(None, book.filter_by_type(OrderType::Fixed).get_lowest_working_price());
+
+    let empty = client.get_order_book(Location::Europe, Algorithm::Scrypt).unwrap();
+    assert!(empty.is_empty());
+    assert_eq!(None, empty.get_price_for_share(0.5));
+    assert_eq!(None, empty.get_lowest_working_price());
+}
+
+#[test]
+fn it_price_estimate() {
+    let server = MockServer::start().unwrap();
+    let _ = server.add_order(order(OrderType::Standard, "0.04", 1.0, 2));
+    let _ = server.add_order(order(OrderType::Standard, "0.05", 2.0, 4));
+    let _ = server.add_order(order(OrderType::Standard, "0.03", 0.0, 0));
+    let _ = server.add_order(order(OrderType::Fixed, "0.06", 4.0, 5));
+    let client = server.client().unwrap();
+
+    let estimate = client.estimate_price(Location::Europe, Algorithm::X11, mh(0.5)).unwrap();
+    assert_eq!(Algorithm::X11, estimate.get_algorithm());
+    assert_eq!(btc("0.041"), estimate.get_price());
+    assert_eq!(mh(0.5), estimate.get_limit());
+    assert_eq!(Some(btc("0.04")), estimate.get_lowest_working_price());
+    assert_eq!(1.0, estimate.get_outbid_speed());
+
+    let estimate = client.estimate_price(Location::Europe, Algorithm::X11, mh(2.0)).unwrap();
+    assert_eq!(btc("0.051"), estimate.get_price());
+    assert_eq!(3.0, estimate.get_outbid_speed());
+
+    let estimate = client.estimate_price(Location::Europe, Algorithm::X11, mh(10.0)).unwrap();
+    assert_eq!(btc("0.051"), estimate.get_price());
+    assert!(estimate.get_outbid_speed() < 10.0);
+
+    let estimate = client.estimate_price(Location::Europe, Algorithm::X11, mh(0.001)).unwrap();
+    assert_eq!(btc("0.041"), estimate.get_price());
+    assert_eq!(mh(0.01), estimate.get_limit());
+    let new_order = estimate.to_new_order(btc("0.1"));
+    assert_eq!(Algorithm::X11, new_order.algorithm);
+    assert_eq!(btc("0.041"), new_order.price);
+    assert_eq!(Some(mh(0.01)), new_order.limit);
+
+    let estimate = client.estimate_price(Location::USA, Algorithm::X11, mh(1.0)).unwrap();
+    assert_eq!(None, estimate.get_lowest_working_price());
+    assert_eq!(btc("0.001"), estimate.get_price());
+}
+
+#[test]
+fn it_price_estimate_empty_levels() {
+    let server = MockServer::start().unwrap();
+    let _ = server.add_order(order(OrderType::Standard, "0.04", 1.0, 2));
+    let _ = server.add_order(order(OrderType::Standard, "0.05", 0.0, 0));
+    let _ = server.add_order(order(OrderType::Standard, "0.06", 0.0, 0));
+    let _ = server.add_order(order(OrderType::Standard, "0.07", 2.0, 3));
+    let client = server.client().unwrap();
+
+    // Orders without hashrate above the target speed don't have to be outbid.
+    let estimate = client.estimate_price(Location::Europe, Algorithm::X11, mh(1.0)).unwrap();
+    assert_eq!(btc("0.041"), estimate.get_price());
+    assert_eq!(1.0, estimate.get_outbid_speed());
+}
@@ -0,0 +1,117 @@
+#![cfg(feature = "mock")]
+
+extern crate nicehash;
+
+mod common;
+
+use nicehash::{Credentials, Location, Algorithm};
+use nicehash::mock::MockOrder;
+use nicehash::order_manager::OrderManager;
+
+use common::{TEST_API_ID, TEST_READ_API_KEY, btc, new_order, authenticated, create_order,
+             mock_server};
+
+#[test]
+fn it_order_manager() {
+    let server = mock_server();
+    let mut competitor = MockOrder::new(Location::Europe, Algorithm::X11, btc("0.04"));
+    competitor.accepted_speed = 1.0;
+    competitor.workers = 2;
+    let competitor = server.add_order(competitor);
+
+    let client = server.client().unwrap();
+    let authenticated = authenticated(&client);
+    let id = create_order(&authenticated, new_order("0.5", "0.06"));
+
+    let mut manager = OrderManager::new(authenticated, Location::Europe, Algorithm::X11)
+        .max_price(btc("0.07"));
+    manager.manage(id);
+    assert_eq!(vec![id], manager.get_managed_orders());
+
+    // The order is too expensive, so its price is decreased by one step.
+    let update = manager.update().unwrap();
+    assert!(update.get_error().is_none());
+    let changes = update.get_changes();
+    assert_eq!(1, changes.len());
+    assert_eq!(id, changes[0].get_order_id());
+    assert!(!changes[0].is_increase());
+    assert_eq!(btc("0.059"), changes[0].get_new_price());
+    assert!(manager.get_last_decrease(id).is_some());
+
+    // The price cannot be decreased again until the down time passes.
+    assert!(manager.update().unwrap().get_changes().is_empty());
+    let _ = server.update_order(id, |order| order.workers = 3);
+    assert!(manager.update().unwrap().get_changes().is_empty());
+
+    // Losing the workers raises the price immediately.
+    let _ = server.update_order(id, |order| order.workers = 0);
+    let update = manager.update().unwrap();
+    assert!(update.get_error().is_none());
+    let changes = update.get_changes();
+    assert_eq!(1, changes.len());
+    assert!(changes[0].is_increase());
+    assert_eq!(btc("0.06"), server.get_order(id).unwrap().price);
+
+    // Being outbid raises the price, up to the maximum price.
+    let _ = server.update_order(competitor, |order| order.price = btc("0.08"));
+    let update = manager.update().unwrap();
+    assert!(update.get_error().is_none());
+    let changes = update.get_changes();
+    assert_eq!(1, changes.len());
+    assert_eq!(btc("0.07"), changes[0].get_new_price());
+    assert_eq!(btc("0.07"), server.get_order(id).unwrap().price);
+
+    assert!(manager.unmanage(id));
+    assert!(manager.update().unwrap().get_changes().is_empty());
+    assert!(manager.get_managed_orders().is_empty());
+}
+
+#[test]
+fn it_order_manager_external_decrease() {
+    let server = mock_server();
+    let mut competitor = MockOrder::new(Location::Europe, Algorithm::X11, btc("0.04"));
+    competitor.workers = 2;
+    let _ = server.add_order(competitor);
+
+    let client = server.client().unwrap();
+    let authenticated = authenticated(&client);
+    let id = create_order(&authenticated, new_order("0.5", "0.06"));
+    let _ = authenticated.decrease_order_price(Location::Europe, Algorithm::X11, id).unwrap();
+
+    // The API rejects the decrease, and the manager waits for the down time before retrying.
+    let mut manager = OrderManager::new(authenticated, Location::Europe, Algorithm::X11);
+    manager.manage(id);
+    assert!(manager.get_last_decrease(id).is_none());
+    assert!(manager.update().unwrap().get_changes().is_empty());
+    assert!(manager.get_last_decrease(id).is_some());
+    assert_eq!(btc("0.059"), server.get_order(id).unwrap().price);
+}
+
+#[test]
+fn it_order_manager_errors() {
+    let server = mock_server();
+    let mut competitor = MockOrder::new(Location::Europe, Algorithm::X11, btc("0.04"));
+    competitor.workers = 2;
+    let _ = server.add_order(competitor);
+
+    let client = server.client().unwrap();
+    let cheap = create_order(&authenticated(&client), new_order("0.5", "0.03"));
+    let dead = create_order(&authenticated(&client), new_order("0.5", "0.03"));
+    let _ = server.update_order(dead, |order| order.alive = false);
+
+    // The read-only key can't change prices, but removed and dead orders stop being managed.
+    let read_only = client.authenticated(Credentials::new(TEST_API_ID, TEST_READ_API_KEY));
+    let mut manager = OrderManager::new(read_only, Location::Europe, Algorithm::X11);
+    manager.manage(cheap);
+    manager.manage(dead);
+    manager.manage(cheap + dead + 1);
+    let update = manager.update().unwrap();
+    assert!(update.get_changes().is_empty());
+    match update.get_error() {
+        Some(&nicehash::error::Error::ReadOnlyKey(_)) => {}
+        r => panic!("unexpected result: {:?}", r),
+    }
+    assert_eq!(vec![cheap], manager.get_managed_orders());
+    assert_eq!(btc("0.03"), server.get_order(cheap).unwrap().price);
+    assert_eq!(btc("0.03"), server.get_order(dead).unwrap().price);
+}
@@ -0,0 +1,59 @@
+#![cfg(feature = "mock")]
+
+extern crate nicehash;
+
+use nicehash::{Location, Algorithm};
+use nicehash::hashrate::{Hashrate, HashrateUnit};
+use nicehash::mock::{MockServer, MockOrder};
+use nicehash::profit::ProfitAdvisor;
+
+#[test]
+fn it_profit_advisor() {
+    let server = MockServer::start().unwrap();
+    for &(algorithm, price) in &[(Algorithm::X11, "0.05"),
+                                 (Algorithm::Equihash, "0.02"),
+                                 (Algorithm::SHA256, "0.01")] {
+        let mut order = MockOrder::new(Location::Europe, algorithm, price.parse().unwrap());
+        order.accepted_speed = 1.0;
+        let _ = server.add_order(order);
+    }
+    let client = server.client().unwrap();
+
+    let advisor = ProfitAdvisor::new()
+        .hashrate(Algorithm::X11, Hashrate::new(10.0, HashrateUnit::MegaHash))
+        .hashrate(Algorithm::SHA256, Hashrate::new(1.0, HashrateUnit::TeraHash))
+        .hashrate(Algorithm::Equihash, Hashrate::new(50.0, HashrateUnit::Solution))
+        .hashrate(Algorithm::SHA256, Hashrate::new(10.0, HashrateUnit::GigaHash));
+    assert_eq!(Some(Hashrate::new(10.0, HashrateUnit::GigaHash)),
+               advisor.get_hashrate(Algorithm::SHA256));
+    assert_eq!(None, advisor.get_hashrate(Algorithm::Scrypt));
+
+    let ranking = client.rank_algorithms(None, &advisor).unwrap();
+    assert_eq!(vec![Algorithm::Equihash, Algorithm::X11, Algorithm::SHA256],
+               ranking.iter().map(|profit| profit.get_algorithm()).collect::<Vec<_>>());
+    assert!((ranking[0].get_btc_per_day() - 1.0).abs() < 1e-8);
+    assert!((ranking[0].get_net_btc_per_day() - 0.999).abs() < 1e-8);
+    assert!((ranking[1].get_net_btc_per_day() - 0.4995).abs() < 1e-8);
+    // The withdrawal fee is higher than the earnings of a day.
+    assert!(ranking[2].get_net_btc_per_day() < 0.0);
+
+    let ranking = client.rank_algorithms(Some(Location::Europe), &advisor.withdrawal_days(10.0))
+        .unwrap();
+    assert!((ranking[2].get_net_btc_per_day() - 0.00005).abs() < 1e-8);
+    assert!(client.rank_algorithms(Some(Location::USA), &ProfitAdvisor::new())
+        .unwrap()
+        .is_empty());
+
+    // Equihash speeds are measured in solutions, not hashes.
+    let advisor = ProfitAdvisor::new()
+        .hashrate(Algorithm::Equihash, Hashrate::new(50.0, HashrateUnit::MegaHash));
+    assert!(client.rank_algorithms(None, &advisor).is_err());
+
+    // The earnings of a withdrawal period must fit in a BTC amount.
+    let advisor = ProfitAdvisor::new()
+        .hashrate(Algorithm::X11, Hashrate::new(1e30, HashrateUnit::MegaHash));
+    match client.rank_algorithms(None, &advisor) {
+        Err(nicehash::error::Error::Overflow(_)) => {}
+        r => panic!("unexpected result: {:?}", r),
+    }
+}
@@ -0,0 +1,124 @@
+#![cfg(feature = "mock")]
+
+extern crate nicehash;
+
+mod common;
+
+use std::io::Read;
+
+use nicehash::{ClientBuilder, Location, Algorithm};
+use nicehash::error::{Result, Error};
+use nicehash::refill::{AutoRefill, RefillPolicy};
+use nicehash::transport::{Transport, HttpTransport, Url};
+
+use common::{TEST_API_ID, TEST_API_KEY, btc, new_order, authenticated, create_order, mock_server};
+
+#[test]
+fn it_auto_refill() {
+    let server = mock_server();
+    let client = server.client().unwrap();
+    let id = create_order(&authenticated(&client), new_order("0.05", "0.05"));
+    let orders = authenticated(&client).get_my_orders(Location::Europe, Algorithm::X11).unwrap();
+    assert_eq!(Some(btc("0.05")), orders[0].get_btc_available());
+
+    let policy = RefillPolicy::new(btc("0.02"), btc("0.1")).daily_cap(btc("0.25"));
+    assert_eq!(Some(btc("0.103")), policy.get_refill_cost());
+    let mut refill = AutoRefill::new(authenticated(&client), policy);
+
+    // The order still has enough funds.
+    assert!(refill.check(Location::Europe, Algorithm::X11).unwrap().get_refills().is_empty());
+
+    let _ = server.update_order(id, |order| order.amount = btc("0.01"));
+    let check = refill.check(Location::Europe, Algorithm::X11).unwrap();
+    assert!(check.get_error().is_none());
+    let refills = check.get_refills();
+    assert_eq!(1, refills.len());
+    assert_eq!(id, refills[0].get_order_id());
+    assert_eq!(btc("0.01"), refills[0].get_previous_btc_available());
+    assert_eq!(btc("0.11"), server.get_order(id).unwrap().amount);
+    assert!(refill.check(Location::Europe, Algorithm::X11).unwrap().get_refills().is_empty());
+
+    let _ = server.update_order(id, |order| order.amount = btc("0.01"));
+    assert_eq!(1, refill.check(Location::Europe, Algorithm::X11).unwrap().get_refills().len());
+    assert_eq!(btc("0.206"), refill.get_spent_today());
+
+    // The next refill would exceed the daily cap.
+    let _ = server.update_order(id, |order| order.amount = btc("0.01"));
+    assert!(refill.check(Location::Europe, Algorithm::X11).unwrap().get_refills().is_empty());
+    let balance = client.get_balance(TEST_API_ID, TEST_API_KEY).unwrap();
+    assert_eq!(btc("0.744"), balance.confirmed);
+
+    // The balance must also pay the fees after the refill.
+    let policy = RefillPolicy::new(btc("0.02"), btc("0.1")).reserve(btc("0.7"));
+    assert!(AutoRefill::new(authenticated(&client), policy)
+        .check(Location::Europe, Algorithm::X11)
+        .unwrap()
+        .get_refills()
+        .is_empty());
+
+    let policy = RefillPolicy::new(btc("0.02"), btc("0.1"));
+    let mut refill = AutoRefill::new(authenticated(&client), policy);
+    server.set_balance(TEST_API_ID, btc("0.1035"), btc("0.0"));
+    assert!(refill.check(Location::Europe, Algorithm::X11).unwrap().get_refills().is_empty());
+    server.set_balance(TEST_API_ID, btc("0.2"), btc("0.0"));
+    assert_eq!(1, refill.check(Location::Europe, Algorithm::X11).unwrap().get_refills().len());
+}
+
+/// Transport that fails the refills of the given order.
+#[derive(Debug)]
+struct FailingRefillTransport {
+    transport: HttpTransport,
+    order_id: u64,
+}
+
+impl Transport for FailingRefillTransport {
+    fn get(&self, url: Url) -> Result<Box<Read>> {
+        let order = format!("{}", self.order_id);
+        let mut pairs = url.query_pairs();
+        if pairs.any(|(key, value)| key == "method" && value == "orders.refill") &&
+           url.query_pairs().any(|(key, value)| key == "order" && value == order) {
+            return Err(Error::Api("connection reset".to_owned()));
+        }
+        self.transport.get(url)
+    }
+}
+
+#[test]
+fn it_auto_refill_partial() {
+    let server = mock_server();
+    let client = server.client().unwrap();
+    let mut ids = Vec::new();
+    for alive in &[true, false, true, true] {
+        let id = create_order(&authenticated(&client), new_order("0.05", "0.05"));
+        let _ = server.update_order(id, |order| {
+            order.amount = btc("0.01");
+            order.alive = *alive;
+        });
+        ids.push(id);
+    }
+
+    // The refill of the third order will fail.
+    let client = ClientBuilder::new()
+        .api_url(server.get_api_url())
+        .transport(FailingRefillTransport {
+            transport: HttpTransport::new(None),
+            order_id: ids[2],
+        })
+        .build()
+        .unwrap();
+    let policy = RefillPolicy::new(btc("0.02"), btc("0.1"));
+    let mut refill = AutoRefill::new(authenticated(&client), policy);
+
+    // The dead order is skipped, and the check stops at the failed refill.
+    let check = refill.check(Location::Europe, Algorithm::X11).unwrap();
+    match check.get_error() {
+        Some(&Error::Api(_)) => {}
+        e => panic!("unexpected error: {:?}", e),
+    }
+    assert_eq!(1, check.get_refills().len());
+    assert_eq!(ids[0], check.get_refills()[0].get_order_id());
+    assert_eq!(btc("0.103"), refill.get_spent_today());
+    assert_eq!(btc("0.11"), server.get_order(ids[0]).unwrap().amount);
+    assert_eq!(btc("0.01"), server.get_order(ids[1]).unwrap().amount);
+    assert_eq!(btc("0.01"), server.get_order(ids[3]).unwrap().amount);
+}
@@ -0,0 +1,226 @@
+#![cfg(all(feature = "mock", feature = "serde"))]
+
+extern crate nicehash;
+extern crate serde;
+extern crate serde_json;
+
+mod common;
+
+use std::fmt::Debug;
+use std::time::{Duration, UNIX_EPOCH};
+
+use serde::{Serialize, Deserialize};
+
+use nicehash::{Location, Algorithm, Btc};
+use nicehash::hashrate::{Hashrate, HashrateUnit};
+use nicehash::mock::{MockServer, MockOrder, MockProviderStat, MockPayment, MockWorker};
+use nicehash::types::{GlobalStats, NewOrder, Balance, BuyInfo, Order, ProviderStatsEx,
+                      ProviderWorker};
+
+use common::{TEST_BTC_ADDRESS, btc, test_pool};
+
+/// Checks that the value is deserialized back to an equal value, with the same JSON.
+fn assert_round_trip<T: Serialize + Deserialize + PartialEq + Debug>(value: &T) {
+    let json = serde_json::to_string(value).unwrap();
+    let parsed: T = serde_json::from_str(&json).unwrap();
+    assert_eq!(value, &parsed);
+    assert_eq!(json, serde_json::to_string(&parsed).unwrap());
+}
+
+/// Checks that the API payload is parsed and serialized back to the same JSON value.
+fn assert_wire_round_trip<T: Serialize + Deserialize>(payload: &str) {
+    let payload: serde_json::Value = serde_json::from_str(payload).unwrap();
+    let parsed: T = serde_json::from_value(payload.clone()).unwrap();
+    assert_same_json(&payload, &serde_json::to_value(&parsed));
+}
+
+/// Compares two JSON values, taking numbers in strings with the same value as equal, since the
+/// API does not always use the same number of decimals.
+fn assert_same_json(expected: &serde_json::Value, found: &serde_json::Value) {
+    use serde_json::Value;
+
+    match (expected, found) {
+        (&Value::Object(ref expected), &Value::Object(ref found)) => {
+            assert_eq!(expected.keys().collect::<Vec<_>>(),
+                       found.keys().collect::<Vec<_>>());
+            for (key, value) in expected {
+                assert_same_json(value, &found[key]);
+            }
+        }
+        (&Value::Array(ref expected), &Value::Array(ref found)) => {
+            assert_eq!(expected.len(), found.len());
+            for (expected, found) in expected.iter().zip(found) {
+                assert_same_json(expected, found);
+            }
+        }
+        (&Value::String(ref expected), &Value::String(ref found)) => {
+            match (expected.parse::<f64>(), found.parse::<f64>()) {
+                (Ok(expected), Ok(found)) => assert_eq!(expected, found),
+                _ => assert_eq!(expected, found),
+            }
+        }
+        (expected, found) => assert_eq!(expected, found),
+    }
+}
+
+#[test]
+fn it_serde_wire_format() {
+    assert_wire_round_trip::<ProviderWorker>(r#"["rig1",{"a":"12.5","rs":"0.1","rd":"0.05"},
+                                                 63,1,"0.5",0,3]"#);
+    assert_wire_round_trip::<ProviderStatsEx>(r#"{
+        "current":[{"profitability":"0.00030512","data":[{"a":"12.5","rs":"0.2","ro":"0.05"},
+                    "0.00051234"],"name":"X11","suffix":"MH","algo":3}],
+        "past":[{"data":[[4987654,{"a":"12.01","rt":"0.3"},"0.00049"]],"algo":3}],
+        "addr":"17a212wdrvEXWuipCV5gcfxdALfMdhMoqh",
+        "payments":[{"amount":"0.01","fee":"0.0002","TXID":"txid","time":1500000000}]
+    }"#);
+    assert_wire_round_trip::<Order>(r#"{"id":1234,"type":0,"limit_speed":"0","alive":true,
+                                        "price":"0.0150","workers":3,"algo":3,
+                                        "accepted_speed":"0.0123","btc_avail":"0.01000000"}"#);
+    assert_wire_round_trip::<NewOrder>(r#"{"algo":3,"amount":"0.5","limit":"1.5",
+                                           "price":"0.1"}"#);
+
+    let algorithms = Algorithm::all()
+        .iter()
+        .map(|alg| {
+            format!(r#"{{"down_step":"-0.0001","min_limit":"0.01","speed_text":"GH",
+                        "multi":"1","algo":{}}}"#,
+                    *alg as u64)
+        })
+        .collect::<Vec<_>>();
+    assert_wire_round_trip::<BuyInfo>(&format!(r#"{{"algorithms":[{}],"down_time":600,
+                                                   "min_amount":"0.01"}}"#,
+                                               algorithms.join(",")));
+
+    let worker: ProviderWorker =
+        serde_json::from_str(r#"["rig1",{"a":"12.5","rs":"0.1","rd":"0.05"},63,1,"0.5",0,3]"#)
+            .unwrap();
+    assert!(worker.is_xnsub());
+    assert!((worker.get_rejected_speed() - 0.15).abs() < 1e-12);
+}
+
+#[test]
+fn it_serde_order() {
+    let server = MockServer::start().unwrap();
+    let mut order = MockOrder::new(Location::Europe, Algorithm::Equihash, btc("0.0123"));
+    order.accepted_speed = 1.5;
+    order.workers = 3;
+    let _ = server.add_order(order);
+    let client = server.client().unwrap();
+
+    let orders = client.get_orders(Location::Europe, Algorithm::Equihash).unwrap();
+    assert_eq!(3, orders[0].get_workers());
+    assert_round_trip(&orders[0]);
+}
+
+#[test]
+fn it_serde_api_info() {
+    let server = MockServer::start().unwrap();
+    let client = server.client().unwrap();
+
+    let info = client.get_buy_info().unwrap();
+    assert_round_trip(&info);
+    assert_round_trip(info.get_buy_info_for(Algorithm::Equihash));
+
+    let stats = client.global_stats_current(None).unwrap();
+    assert_round_trip(&stats);
+    assert_round_trip(stats.get_stats_for(Algorithm::X11));
+    assert_round_trip(&client.global_stats_24h().unwrap());
+    assert_round_trip(&client.multialgo_info().unwrap());
+
+    // Every algorithm must be in the global stats once.
+    let mut stats = serde_json::to_value(&stats).as_array().unwrap().clone();
+    stats[1] = stats[0].clone();
+    assert!(serde_json::from_value::<GlobalStats>(serde_json::Value::Array(stats)).is_err());
+}
+
+#[test]
+fn it_serde_provider() {
+    let server = MockServer::start().unwrap();
+    server.set_provider_stat(TEST_BTC_ADDRESS,
+                             MockProviderStat {
+                                 algorithm: Algorithm::X11,
+                                 balance: btc("0.0005"),
+                                 accepted_speed: 12.5,
+                                 rejected_speed: 0.25,
+                                 profitability: 0.0003,
+                             });
+    server.add_provider_payment(TEST_BTC_ADDRESS,
+                                MockPayment {
+                                    amount: btc("0.01"),
+                                    fee: btc("0.0001"),
+                                    transaction_id: "txid".to_owned(),
+                                    time: UNIX_EPOCH + Duration::from_secs(1_500_000_000),
+                                });
+    server.add_provider_worker(TEST_BTC_ADDRESS,
+                               MockWorker {
+                                   name: "rig1".to_owned(),
+                                   algorithm: Algorithm::X11,
+                                   accepted_speed: 12.5,
+                                   rejected_speed: 0.25,
+                                   connected_time: Duration::from_secs(3600),
+                                   difficulty: 0.5,
+                                   location: Location::Europe,
+                               });
+    let client = server.client().unwrap();
+
+    let stats = client.provider_stats(TEST_BTC_ADDRESS).unwrap();
+    assert_round_trip(&stats);
+    assert_round_trip(&stats.get_all_stats()[0]);
+
+    let stats = client.provider_stats_ex(TEST_BTC_ADDRESS, None).unwrap();
+    assert_eq!(Some(TEST_BTC_ADDRESS), stats.get_addr());
+    assert_eq!(1, stats.get_payments().len());
+    assert_round_trip(&stats);
+    assert_round_trip(&stats.get_current()[0]);
+    assert_round_trip(&stats.get_history()[0]);
+    assert_round_trip(&stats.get_history()[0].get_samples()[0]);
+
+    assert_round_trip(&client.provider_payments(TEST_BTC_ADDRESS).unwrap()[0]);
+    assert_round_trip(&client.provider_workers(TEST_BTC_ADDRESS, None).unwrap()[0]);
+}
+
+#[test]
+fn it_serde_request_types() {
+    let order = NewOrder {
+        algorithm: Algorithm::X11,
+        amount: Btc::from_satoshis(50_000_000),
+        price: Btc::from_satoshis(1),
+        limit: None,
+    };
+    let json = serde_json::to_string(&order).unwrap();
+    assert_eq!(r#"{"algo":3,"amount":"0.50000000","limit":"0","price":"0.00000001"}"#, json);
+    assert_round_trip(&order);
+
+    // Limits are sent to the API as bare numbers in the native unit of the algorithm.
+    let order = NewOrder { limit: Some(Hashrate::new(1.5, HashrateUnit::GigaHash)), ..order };
+    assert!(serde_json::to_string(&order).is_err());
+    let order = NewOrder { limit: Some(Hashrate::new(1.5, HashrateUnit::Native)), ..order };
+    let json = serde_json::to_string(&order).unwrap();
+    assert_eq!(r#"{"algo":3,"amount":"0.50000000","limit":"1.5","price":"0.00000001"}"#, json);
+    assert_round_trip(&order);
+
+    let server = MockServer::start().unwrap();
+    let buy_info = server.client().unwrap().get_buy_info().unwrap();
+    let order = NewOrder {
+        algorithm: Algorithm::SHA256,
+        limit: Some(Hashrate::new(1500.0, HashrateUnit::GigaHash)),
+        ..order
+    };
+    let order = order.with_native_limit(&buy_info).unwrap();
+    assert_eq!(Some(Hashrate::new(1.5, HashrateUnit::Native)), order.limit);
+
+    let pool = test_pool();
+    assert_round_trip(&pool);
+
+    let balance: Balance =
+        serde_json::from_str(r#"{"balance_confirmed":"0.00500000","balance_pending":"0.1"}"#)
+            .unwrap();
+    assert_eq!(Btc::from_satoshis(500_000), balance.confirmed);
+    assert_eq!(Btc::from_satoshis(10_000_000), balance.pending);
+    assert_round_trip(&balance);
+    assert!(serde_json::from_str::<Balance>(r#"{"balance_confirmed":0.5}"#).is_err());
+    assert!(serde_json::from_str::<Balance>(r#"{"balance_confirmed":"0.000000001",
+                                                "balance_pending":"0"}"#)
+        .is_err());
+}
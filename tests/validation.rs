@@ -0,0 +1,170 @@
+#![cfg(feature = "mock")]
+
+extern crate nicehash;
+
+mod common;
+
+use nicehash::{ClientBuilder, Location, Algorithm, Btc};
+use nicehash::hashrate::{Hashrate, HashrateUnit};
+use nicehash::mock::MockServer;
+use nicehash::types::{NewOrder, DEFAULT_MAX_POOL_USERNAME_LENGTH};
+
+use common::{btc, test_pool, new_order, authenticated, mock_server};
+
+#[test]
+fn it_validate_new_order() {
+    let server = MockServer::start().unwrap();
+    let client = server.client().unwrap();
+    let buy_info = client.get_buy_info().unwrap();
+
+    let mut order = NewOrder {
+        limit: Some(Hashrate::new(0.5, HashrateUnit::MegaHash)),
+        ..new_order("0.05", "0.05")
+    };
+    order.validate(&buy_info).unwrap();
+
+    order.limit = None;
+    order.validate(&buy_info).unwrap();
+
+    order.limit = Some(Hashrate::new(9.0, HashrateUnit::KiloHash));
+    match order.validate(&buy_info) {
+        Err(nicehash::error::Error::LimitBelowMinimum(_)) => {}
+        r => panic!("unexpected result: {:?}", r),
+    }
+
+    order.limit = Some(Hashrate::new(1.0, HashrateUnit::Solution));
+    match order.validate(&buy_info) {
+        Err(nicehash::error::Error::ParseHashrate(_)) => {}
+        r => panic!("unexpected result: {:?}", r),
+    }
+
+    let min_amount = buy_info.get_min_amount().unwrap();
+    assert_eq!(btc("0.01"), min_amount);
+    order.limit = Some(Hashrate::new(10.0, HashrateUnit::KiloHash));
+    order.amount = min_amount;
+    order.validate(&buy_info).unwrap();
+
+    order.amount = min_amount - Btc::from_satoshis(1);
+    match order.validate(&buy_info) {
+        Err(nicehash::error::Error::AmountBelowMinimum(_)) => {}
+        r => panic!("unexpected result: {:?}", r),
+    }
+}
+
+#[test]
+fn it_validate_pool() {
+    test_pool().validate(None).unwrap();
+
+    let mut pool = test_pool();
+    pool.host = String::new();
+    match pool.validate(None) {
+        Err(nicehash::error::Error::InvalidPoolHost(_)) => {}
+        r => panic!("unexpected result: {:?}", r),
+    }
+
+    let mut pool = test_pool();
+    pool.port = 0;
+    match pool.validate(None) {
+        Err(nicehash::error::Error::InvalidPoolPort(_)) => {}
+        r => panic!("unexpected result: {:?}", r),
+    }
+
+    let mut pool = test_pool();
+    pool.username = ::std::iter::repeat('a').take(64).collect();
+    pool.validate(None).unwrap();
+    pool.validate(Some(64)).unwrap();
+    pool.username.push('a');
+    pool.validate(None).unwrap();
+    match pool.validate(Some(64)) {
+        Err(nicehash::error::Error::PoolUsernameTooLong(_)) => {}
+        r => panic!("unexpected result: {:?}", r),
+    }
+
+    pool.username = ::std::iter::repeat('a').take(DEFAULT_MAX_POOL_USERNAME_LENGTH + 1).collect();
+    match pool.validate(None) {
+        Err(nicehash::error::Error::PoolUsernameTooLong(_)) => {}
+        r => panic!("unexpected result: {:?}", r),
+    }
+    pool.validate(Some(DEFAULT_MAX_POOL_USERNAME_LENGTH + 1)).unwrap();
+}
+
+#[test]
+fn it_create_order_invalid_pool() {
+    let server = mock_server();
+    let client = server.client().unwrap();
+    let authenticated = authenticated(&client);
+
+    let order = new_order("0.05", "0.05");
+    let mut pool = test_pool();
+    pool.port = 0;
+    match authenticated.create_order(Location::Europe, order, pool, None) {
+        Err(nicehash::error::Error::InvalidPoolPort(_)) => {}
+        r => panic!("unexpected result: {:?}", r),
+    }
+    assert!(authenticated.get_my_orders(Location::Europe, Algorithm::X11).unwrap().is_empty());
+}
+
+#[test]
+fn it_create_order_invalid_order() {
+    let server = mock_server();
+    let client = server.client().unwrap();
+    let authenticated = authenticated(&client);
+    let buy_info = client.get_buy_info().unwrap();
+
+    let order = new_order("0.005", "0.05");
+    match authenticated.create_order_checked(Location::Europe,
+                                             order,
+                                             test_pool(),
+                                             None,
+                                             &buy_info) {
+        Err(nicehash::error::Error::AmountBelowMinimum(_)) => {}
+        r => panic!("unexpected result: {:?}", r),
+    }
+
+    let order = NewOrder {
+        limit: Some(Hashrate::new(1.0, HashrateUnit::KiloHash)),
+        ..new_order("0.05", "0.05")
+    };
+    match authenticated.create_order_checked(Location::Europe,
+                                             order,
+                                             test_pool(),
+                                             None,
+                                             &buy_info) {
+        Err(nicehash::error::Error::LimitBelowMinimum(_)) => {}
+        r => panic!("unexpected result: {:?}", r),
+    }
+
+    // The limit of a valid order is converted with the given buy information.
+    let order = NewOrder {
+        limit: Some(Hashrate::new(10.0, HashrateUnit::KiloHash)),
+        ..new_order("0.05", "0.05")
+    };
+    let id = authenticated.create_order_checked(Location::Europe,
+                              order,
+                              test_pool(),
+                              None,
+                              &buy_info)
+        .unwrap();
+    assert_eq!(0.01, server.get_order(id).unwrap().limit_speed);
+    authenticated.remove_order(Location::Europe, Algorithm::X11, id).unwrap();
+    assert!(authenticated.get_my_orders(Location::Europe, Algorithm::X11).unwrap().is_empty());
+}
+
+#[test]
+fn it_create_order_max_pool_username_length() {
+    let server = mock_server();
+    let client = ClientBuilder::new()
+        .api_url(server.get_api_url())
+        .max_pool_username_length(4)
+        .build()
+        .unwrap();
+    assert_eq!(Some(4), client.get_max_pool_username_length());
+    assert_eq!(None, server.client().unwrap().get_max_pool_username_length());
+    let authenticated = authenticated(&client);
+
+    let order = new_order("0.05", "0.05");
+    match authenticated.create_order(Location::Europe, order, test_pool(), None) {
+        Err(nicehash::error::Error::PoolUsernameTooLong(_)) => {}
+        r => panic!("unexpected result: {:?}", r),
+    }
+}